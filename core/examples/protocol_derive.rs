@@ -24,6 +24,16 @@ mod derive_main {
         val: Option<i32>,
     }
 
+    #[derive(cbor_protocol, Eq, PartialEq, Debug)]
+    struct OmittingStruct {
+        #[id(1)]
+        #[omit_none]
+        val: Option<i32>,
+        #[id(2)]
+        #[default("none")]
+        name: String,
+    }
+
     #[derive(cbor_protocol, Eq, PartialEq, Debug)]
     enum BlaEnum {
         #[id(1)]
@@ -90,6 +100,16 @@ mod derive_main {
         bytes: &'a [u8],
     }
 
+    #[derive(cbor_protocol, PartialEq, Debug)]
+    struct StructWithPayload<'a> {
+        #[id(1)]
+        payload: OwnedValue,
+        #[id(2)]
+        values: Vec<Value<'a>>,
+        #[id(3)]
+        extra: Option<OwnedValue>,
+    }
+
     pub fn derive_main() {
         let bla = BlaStruct {
             name: "hello world".into(),
@@ -114,6 +134,30 @@ mod derive_main {
         test_serialize_and_back(&BlaEnum::ValMultipleName { id: 8, bla: bla.clone(), name: "sauerland!".into() }, b"\xA3\x09\x08\x0A\xA2\x01\x6B\x68\x65\x6C\x6C\x6F\x20\x77\x6F\x72\x6C\x64\x02\x18\x2A\x0B\x6A\x73\x61\x75\x65\x72\x6C\x61\x6E\x64\x21");
         test_serialize_and_back(&BlaTupleStruct(bla.clone(), 42), b"\xA2\x01\xA2\x01\x6B\x68\x65\x6C\x6C\x6F\x20\x77\x6F\x72\x6C\x64\x02\x18\x2A\x02\x18\x2A");
 
+        test_serialize_and_back(&DefaultStruct { val: None }, b"\xA1\x01\xF6");
+        test_serialize_and_back(&BlaEnum::ValOption(None), b"\xA1\x0C\xF6");
+        test_serialize_and_back(
+            &OmittingStruct {
+                val: None,
+                name: "none".into(),
+            },
+            b"\xA0",
+        );
+        test_serialize_and_back(
+            &OmittingStruct {
+                val: Some(1),
+                name: "a".into(),
+            },
+            b"\xA2\x01\x01\x02\x61\x61",
+        );
+        test_serialize_and_back(
+            &BlaStruct {
+                name: "none".into(),
+                value: 1,
+            },
+            b"\xA1\x02\x01",
+        );
+
         let data: Vec<u8> = b"\xCa\xFe\xBa\xbe".as_ref().into();
         let struct_with_bytes = StructWithBytes {
             bytes: data.as_slice(),
@@ -123,6 +167,29 @@ mod derive_main {
         };
         test_serialize_and_back(&struct_with_bytes, b"\xA1\x01\x44\xCA\xFE\xBA\xBE");
         test_serialize_and_back(&struct_with_vec, b"\xA1\x01\x44\xCA\xFE\xBA\xBE");
+
+        let with_unknown: BlaStruct =
+            from_bytes(b"\xA3\x01\x61\x61\x09\x82\x01\x02\x02\x18\x2A").unwrap();
+        assert_eq!(with_unknown.value, 42);
+
+        let error = from_bytes::<BlaStruct>(b"\xA2\x01\x61\x61\x02\x61\x61").unwrap_err();
+        assert_eq!(error.offset(), Some(5));
+        assert_eq!(error.path().unwrap().to_string(), "$.value#2");
+
+        let with_payload = StructWithPayload {
+            payload: Value::Map(vec![(Value::Text("a".into()), Value::F64(1.5))]).into(),
+            values: vec![Value::U64(1), Value::Text("b".into())],
+            extra: Some(Value::Text("c".into()).into()),
+        };
+        let bytes = to_vec(&with_payload);
+        assert_eq!(
+            bytes,
+            b"\xA3\x01\xA1\x61\x61\xFB\x3F\xF8\0\0\0\0\0\0\x02\x82\x01\x61\x62\x03\x61\x63"
+        );
+        assert_eq!(
+            from_bytes::<StructWithPayload>(&bytes).unwrap(),
+            with_payload
+        );
     }
 
     fn test_serialize_and_back<'de, T: Serialize + Deserialize<'de> + Eq + Debug>(
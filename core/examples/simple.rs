@@ -9,10 +9,10 @@ fn main() {
     assert_eq!("Bla", from_bytes::<&str>(vec.as_slice()).unwrap());
 
     let vec = to_vec(&123.4);
-    assert_eq!(123.4, from_bytes(vec.as_slice()).unwrap());
+    assert_eq!(123.4, from_bytes::<f64>(vec.as_slice()).unwrap());
 
     let vec = to_vec(&123.4);
-    assert_eq!(123.4, from_bytes(vec.as_slice()).unwrap());
+    assert_eq!(123.4, from_bytes::<f64>(vec.as_slice()).unwrap());
 
     let option = Some(42);
     let vec = to_vec(&option);
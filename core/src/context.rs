@@ -1,3 +1,118 @@
-use anymap::AnyMap;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 
-pub type Context = AnyMap;
+use crate::ser::FloatEncoding;
+
+/// Options consulted by the built-in `Serialize` and `Deserialize` impls,
+/// plus a `Send + Sync` map for data of user impls keyed by its type.
+#[derive(Debug)]
+pub struct Context {
+    pub datetime_format: DateTimeFormat,
+    pub datetime_precision: Precision,
+    /// Writes slices of numbers as typed arrays (RFC 8746) in the given byte order, needs `iana_std`.
+    pub typed_arrays: Option<Endianness>,
+    pub float_encoding: FloatEncoding,
+    pub option_encoding: OptionEncoding,
+    /// Sorts map entries and writes floats in their shortest width like `Serializer::deterministic`.
+    pub canonical: bool,
+    extensions: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+/// How `DateTime` is written, needs `iana_chrono`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DateTimeFormat {
+    /// Tag 1 or 1001 with the precision of `Context::datetime_precision`.
+    Epoch,
+    /// Tag 0, an RFC 3339 text.
+    Rfc3339,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Precision {
+    Float,
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+/// How `None` is written, both are read as `None`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum OptionEncoding {
+    Null,
+    Undefined,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_datetime_format(mut self, datetime_format: DateTimeFormat) -> Self {
+        self.datetime_format = datetime_format;
+        self
+    }
+    pub fn with_datetime_precision(mut self, datetime_precision: Precision) -> Self {
+        self.datetime_precision = datetime_precision;
+        self
+    }
+    pub fn with_typed_arrays(mut self, typed_arrays: Option<Endianness>) -> Self {
+        self.typed_arrays = typed_arrays;
+        self
+    }
+    pub fn with_float_encoding(mut self, float_encoding: FloatEncoding) -> Self {
+        self.float_encoding = float_encoding;
+        self
+    }
+    pub fn with_option_encoding(mut self, option_encoding: OptionEncoding) -> Self {
+        self.option_encoding = option_encoding;
+        self
+    }
+    pub fn with_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Stores `value` for user impls, returning the previous value of the same type.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.extensions
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.extensions
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.extensions
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.extensions
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            datetime_format: DateTimeFormat::Epoch,
+            datetime_precision: Precision::Nanos,
+            typed_arrays: None,
+            float_encoding: FloatEncoding::Declared,
+            option_encoding: OptionEncoding::Null,
+            canonical: false,
+            extensions: HashMap::new(),
+        }
+    }
+}
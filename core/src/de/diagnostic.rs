@@ -0,0 +1,162 @@
+use std::fmt::Write;
+use std::str::from_utf8;
+
+use nom::bytes::streaming::take;
+
+use crate::de::{Deserializer, Remaining};
+use crate::error::CborError;
+use crate::types::{Length, Special, Type};
+use crate::value::diagnostic::{write_bytes, write_float, write_text};
+
+impl<'de> Deserializer {
+    /// Renders one item in diagnostic notation (RFC 8949 §8), indefinite lengths are marked with `_`
+    /// and floats with the encoding indicators `_1`, `_2` or `_3` of their width.
+    pub fn take_diagnostic(&self, data: &'de [u8]) -> Result<(String, Remaining<'de>), CborError> {
+        let mut out = String::new();
+        let remaining = self.write_diagnostic(&mut out, data)?;
+        Ok((out, remaining))
+    }
+
+    fn write_diagnostic(
+        &self,
+        out: &mut String,
+        data: &'de [u8],
+    ) -> Result<Remaining<'de>, CborError> {
+        let (cbor_type, remaining) = self.take_type(data, false)?;
+        match cbor_type {
+            Type::UnsignedInteger(int) => {
+                let (remaining, value) = int.take_value(remaining)?;
+                write!(out, "{}", value).unwrap();
+                Ok(remaining)
+            }
+            Type::NegativeInteger(int) => {
+                let (remaining, value) = int.take_value(remaining)?;
+                write!(out, "{}", value).unwrap();
+                Ok(remaining)
+            }
+            Type::Bytes(length) | Type::Text(length) => {
+                let (mut remaining, o) = length.take_length_to_read(remaining)?;
+                if let Some(length) = o {
+                    self.check_string_length(length)?;
+                    let (remaining, bytes) = take(length)(remaining)?;
+                    match cbor_type {
+                        Type::Bytes(_) => write_bytes(out, bytes).unwrap(),
+                        _ => write_text(out, from_utf8(bytes)?).unwrap(),
+                    }
+                    return Ok(remaining);
+                }
+                out.push_str("(_ ");
+                let mut first = true;
+                loop {
+                    let (is_break, ret) = self.check_break(remaining, false)?;
+                    if is_break {
+                        out.push(')');
+                        break Ok(ret);
+                    }
+                    let (chunk_type, _) = self.take_type(remaining, false)?;
+                    let same_type = match (cbor_type, chunk_type) {
+                        (Type::Bytes(_), Type::Bytes(length)) => length != Length::Indefinite,
+                        (Type::Text(_), Type::Text(length)) => length != Length::Indefinite,
+                        _ => false,
+                    };
+                    if !same_type {
+                        return Err(CborError::InvalidChunk(chunk_type));
+                    }
+                    if !first {
+                        out.push_str(", ");
+                    }
+                    first = false;
+                    remaining = self.write_diagnostic(out, remaining)?;
+                }
+            }
+            Type::Array(length) | Type::Map(length) => {
+                let is_map = matches!(cbor_type, Type::Map(_));
+                out.push(if is_map { '{' } else { '[' });
+                let (mut remaining, o) = length.take_length_to_read(remaining)?;
+                if o.is_none() {
+                    out.push_str("_ ");
+                }
+                if let Some(length) = o {
+                    self.check_collection_length(length)?;
+                }
+                let mut items = 0;
+                loop {
+                    match o {
+                        Some(length)
+                            if items == length.saturating_mul(if is_map { 2 } else { 1 }) =>
+                        {
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            let (is_break, ret) = self.check_break(remaining, false)?;
+                            if is_break {
+                                remaining = ret;
+                                break;
+                            }
+                        }
+                    }
+                    if items > 0 {
+                        out.push_str(if is_map && items % 2 == 1 { ": " } else { ", " });
+                    }
+                    remaining = self.write_nested_diagnostic(out, data, remaining)?;
+                    items += 1;
+                }
+                if is_map && items % 2 == 1 {
+                    return Err(CborError::UnexpectedBreak);
+                }
+                out.push(if is_map { '}' } else { ']' });
+                Ok(remaining)
+            }
+            Type::Tag(int) => {
+                let (remaining, tag) = int.take_value(remaining)?;
+                write!(out, "{}(", tag).unwrap();
+                let remaining = self.write_nested_diagnostic(out, data, remaining)?;
+                out.push(')');
+                Ok(remaining)
+            }
+            Type::Special(special) => match special {
+                Special::Bool(val) => {
+                    write!(out, "{}", val).unwrap();
+                    Ok(remaining)
+                }
+                Special::Null => {
+                    out.push_str("null");
+                    Ok(remaining)
+                }
+                Special::Undefined => {
+                    out.push_str("undefined");
+                    Ok(remaining)
+                }
+                Special::Simple(val) => {
+                    write!(out, "simple({})", val).unwrap();
+                    Ok(remaining)
+                }
+                Special::Break => Err(CborError::UnexpectedBreak),
+                _ => {
+                    let (value, remaining) = self.take_float(data, false)?;
+                    write_float(out, value).unwrap();
+                    let indicator = match special {
+                        Special::F32 => "_2",
+                        Special::F64 => "_3",
+                        _ => "_1",
+                    };
+                    out.push_str(indicator);
+                    Ok(remaining)
+                }
+            },
+        }
+    }
+
+    fn write_nested_diagnostic(
+        &self,
+        out: &mut String,
+        data: &'de [u8],
+        item: &'de [u8],
+    ) -> Result<Remaining<'de>, CborError> {
+        self.enter_nested(data)?;
+        let result = self.write_diagnostic(out, item);
+        self.leave_nested();
+        result
+    }
+}
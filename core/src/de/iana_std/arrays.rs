@@ -114,7 +114,7 @@ impl<'de> Deserializer {
             };
             Ok((val, ret))
         };
-        self.take_n_array(data, &[Sint16BeArray, Sint16BeArray], 2, func)
+        self.take_n_array(data, &[Sint16BeArray, Sint16LeArray], 2, func)
     }
 
     pub fn take_i32_array(
@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use crate::context::Context;
+use crate::de::{Deserialize, Deserializer, Remaining};
+use crate::error::{CborError, PathSegment};
+
+impl Deserializer {
+    /// Reads an array header and returns an iterator decoding one element per call to `next`.
+    ///
+    /// The array counts as nested item until the iterator is dropped.
+    pub fn take_array_iter<'a, 'de, T: Deserialize<'de>>(
+        &'a mut self,
+        data: &'de [u8],
+        context: &'a Context,
+    ) -> Result<ArrayIter<'a, 'de, T>, CborError> {
+        let (length, remaining) = self.take_array_def(data, true)?;
+        if let Some(length) = length {
+            self.check_collection_length(length)?;
+        }
+        self.enter_nested(data)?;
+        Ok(ArrayIter {
+            deserializer: self,
+            context,
+            elements: Elements::new(length, remaining),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Reads a map header and returns an iterator decoding one entry per call to `next`.
+    pub fn take_map_iter<'a, 'de, K: Deserialize<'de>, V: Deserialize<'de>>(
+        &'a mut self,
+        data: &'de [u8],
+        context: &'a Context,
+    ) -> Result<MapIter<'a, 'de, K, V>, CborError> {
+        let (length, remaining) = self.take_map_def(data, true)?;
+        if let Some(length) = length {
+            self.check_collection_length(length)?;
+        }
+        self.enter_nested(data)?;
+        Ok(MapIter {
+            deserializer: self,
+            context,
+            elements: Elements::new(length, remaining),
+            keys: HashSet::new(),
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct Elements<'de> {
+    length: Option<usize>,
+    remaining: Remaining<'de>,
+    index: usize,
+    finished: bool,
+    failed: bool,
+}
+
+impl<'de> Elements<'de> {
+    fn new(length: Option<usize>, remaining: Remaining<'de>) -> Self {
+        Self {
+            length,
+            remaining,
+            index: 0,
+            finished: false,
+            failed: false,
+        }
+    }
+
+    fn has_next(&mut self, deserializer: &Deserializer) -> Result<bool, CborError> {
+        if self.finished || self.failed {
+            return Ok(false);
+        }
+        match self.length {
+            Some(length) => self.finished = self.index == length,
+            None => {
+                let (is_break, ret) = deserializer.check_break(self.remaining, true)?;
+                if is_break {
+                    self.remaining = ret;
+                    self.finished = true;
+                } else {
+                    deserializer.check_collection_length(self.index + 1)?;
+                }
+            }
+        }
+        Ok(!self.finished)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.length {
+            _ if self.finished || self.failed => (0, Some(0)),
+            Some(length) => (length - self.index, Some(length - self.index)),
+            None => (0, None),
+        }
+    }
+
+    fn next<T, F>(
+        &mut self,
+        deserializer: &mut Deserializer,
+        decode: F,
+    ) -> Option<Result<T, CborError>>
+    where
+        F: FnOnce(&mut Deserializer, Remaining<'de>) -> Result<(T, Remaining<'de>), CborError>,
+    {
+        let result = self.has_next(deserializer).and_then(|has_next| {
+            if !has_next {
+                return Ok(None);
+            }
+            let data = self.remaining;
+            let result = decode(deserializer, data);
+            let index = self.index;
+            let (value, remaining) =
+                deserializer.locate(result, data, || PathSegment::Index(index))?;
+            self.remaining = remaining;
+            self.index += 1;
+            Ok(Some(value))
+        });
+        match result {
+            Ok(value) => value.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Decodes the elements of an array lazily, see `Deserializer::take_array_iter`.
+pub struct ArrayIter<'a, 'de, T> {
+    deserializer: &'a mut Deserializer,
+    context: &'a Context,
+    elements: Elements<'de>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, 'de, T> ArrayIter<'a, 'de, T> {
+    /// The data following the array, available once all elements have been read.
+    pub fn remaining(&self) -> Option<Remaining<'de>> {
+        if self.elements.finished {
+            Some(self.elements.remaining)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'de, T> Drop for ArrayIter<'a, 'de, T> {
+    fn drop(&mut self) {
+        self.deserializer.leave_nested();
+    }
+}
+
+impl<'a, 'de, T: Deserialize<'de>> Iterator for ArrayIter<'a, 'de, T> {
+    type Item = Result<T, CborError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let context = self.context;
+        self.elements.next(self.deserializer, |deserializer, data| {
+            T::deserialize(deserializer, data, context)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
+
+/// Decodes the entries of a map lazily, see `Deserializer::take_map_iter`.
+pub struct MapIter<'a, 'de, K, V> {
+    deserializer: &'a mut Deserializer,
+    context: &'a Context,
+    elements: Elements<'de>,
+    keys: HashSet<&'de [u8]>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'a, 'de, K, V> MapIter<'a, 'de, K, V> {
+    /// The data following the map, available once all entries have been read.
+    pub fn remaining(&self) -> Option<Remaining<'de>> {
+        if self.elements.finished {
+            Some(self.elements.remaining)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'de, K, V> Drop for MapIter<'a, 'de, K, V> {
+    fn drop(&mut self) {
+        self.deserializer.leave_nested();
+    }
+}
+
+impl<'a, 'de, K: Deserialize<'de>, V: Deserialize<'de>> Iterator for MapIter<'a, 'de, K, V> {
+    type Item = Result<(K, V), CborError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let context = self.context;
+        let keys = &mut self.keys;
+        self.elements.next(self.deserializer, |deserializer, data| {
+            let (key, remaining) = K::deserialize(deserializer, data, context)?;
+            let key_bytes = &data[..data.len() - remaining.len()];
+            if deserializer.is_strict() && !keys.insert(key_bytes) {
+                return Err(CborError::DuplicateMapKey(key_bytes.to_vec()));
+            }
+            let result = V::deserialize(deserializer, remaining, context);
+            let (value, remaining) =
+                deserializer.locate(result, remaining, || deserializer.key_segment(key_bytes))?;
+            Ok(((key, value), remaining))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
@@ -0,0 +1,148 @@
+use crate::de::Deserializer;
+use crate::error::CborError;
+
+/// Upper bounds the `Deserializer` enforces while decoding untrusted input.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct DecodeLimits {
+    pub max_depth: usize,
+    pub max_collection_length: usize,
+    /// Shared by all items nested in one top-level item, the count starts over for the next one.
+    pub max_allocated_bytes: usize,
+    pub max_string_length: usize,
+}
+
+impl DecodeLimits {
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_collection_length: usize::MAX,
+            max_allocated_bytes: usize::MAX,
+            max_string_length: usize::MAX,
+        }
+    }
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    pub fn with_max_collection_length(mut self, max_collection_length: usize) -> Self {
+        self.max_collection_length = max_collection_length;
+        self
+    }
+    pub fn with_max_allocated_bytes(mut self, max_allocated_bytes: usize) -> Self {
+        self.max_allocated_bytes = max_allocated_bytes;
+        self
+    }
+    pub fn with_max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = max_string_length;
+        self
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self::unlimited().with_max_depth(128)
+    }
+}
+
+impl Deserializer {
+    pub fn with_limits(limits: DecodeLimits) -> Self {
+        let mut deserializer = Self::new();
+        deserializer.limits = limits;
+        deserializer
+    }
+    pub fn limits(&self) -> &DecodeLimits {
+        &self.limits
+    }
+    pub fn set_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
+    }
+    /// Forgets the nesting depth and the bytes allocated so far, e.g. before decoding the next message.
+    pub fn reset(&self) {
+        self.depth.set(0);
+        self.allocated.set(0);
+    }
+
+    /// Has to be paired with `leave_nested`, also when decoding the nested item failed.
+    ///
+    /// `data` starts at the nested item, error offsets are counted from the outermost one
+    /// unless a top-level entry point like `decode` fixed the start of the input.
+    pub fn enter_nested(&self, data: &[u8]) -> Result<(), CborError> {
+        let depth = self.depth.get() + 1;
+        if depth > self.limits.max_depth {
+            return Err(CborError::LimitExceeded {
+                limit: "max_depth",
+                max: self.limits.max_depth,
+            });
+        }
+        if depth == 1 && !self.anchored.get() {
+            self.input_len.set(data.len());
+        }
+        self.depth.set(depth);
+        Ok(())
+    }
+    pub fn leave_nested(&self) {
+        let depth = self.depth.get().saturating_sub(1);
+        self.depth.set(depth);
+        if depth == 0 && !self.anchored.get() {
+            self.allocated.set(0);
+        }
+    }
+
+    pub fn check_collection_length(&self, length: usize) -> Result<(), CborError> {
+        if length > self.limits.max_collection_length {
+            Err(CborError::LimitExceeded {
+                limit: "max_collection_length",
+                max: self.limits.max_collection_length,
+            })
+        } else {
+            Ok(())
+        }
+    }
+    pub fn check_string_length(&self, length: usize) -> Result<(), CborError> {
+        if length > self.limits.max_string_length {
+            Err(CborError::LimitExceeded {
+                limit: "max_string_length",
+                max: self.limits.max_string_length,
+            })
+        } else {
+            Ok(())
+        }
+    }
+    pub fn reserve_allocation(&self, bytes: usize) -> Result<(), CborError> {
+        let allocated = self.allocated.get().saturating_add(bytes);
+        if allocated > self.limits.max_allocated_bytes {
+            return Err(CborError::LimitExceeded {
+                limit: "max_allocated_bytes",
+                max: self.limits.max_allocated_bytes,
+            });
+        }
+        if self.depth.get() > 0 || self.anchored.get() {
+            self.allocated.set(allocated);
+        }
+        Ok(())
+    }
+
+    /// Checks a collection header and returns the capacity to preallocate.
+    ///
+    /// Every element takes at least one byte, so the capacity never exceeds the remaining input.
+    pub fn reserve_collection<T>(
+        &self,
+        length: Option<usize>,
+        data: &[u8],
+    ) -> Result<usize, CborError> {
+        match length {
+            Some(length) => {
+                self.check_collection_length(length)?;
+                let capacity = length.min(data.len());
+                self.reserve_allocation(capacity.saturating_mul(std::mem::size_of::<T>()))?;
+                Ok(capacity)
+            }
+            None => Ok(0),
+        }
+    }
+    /// Accounts one more element of an indefinite collection which now holds `length` elements.
+    pub fn grow_collection<T>(&self, length: usize) -> Result<(), CborError> {
+        self.check_collection_length(length)?;
+        self.reserve_allocation(std::mem::size_of::<T>())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::context::Context;
+use crate::de::{Deserialize, Deserializer, Remaining};
+use crate::error::{CborError, DecodePath, PathSegment};
+use crate::value::Value;
+
+impl Deserializer {
+    /// Decodes one top-level item, errors carry their offset from the start of `data`.
+    ///
+    /// Unlike calling `T::deserialize` directly, errors outside of nested items are located too,
+    /// at offset 0 with an empty path.
+    pub fn decode<'de, T: Deserialize<'de>>(
+        &mut self,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(T, Remaining<'de>), CborError> {
+        let top_level = self.begin_top_level(data);
+        let result = T::deserialize(self, data, context);
+        self.end_top_level(top_level, result)
+    }
+
+    /// Returns whether `data` starts a top-level item, which has to be passed to `end_top_level`.
+    pub(crate) fn begin_top_level(&self, data: &[u8]) -> bool {
+        if self.anchored.get() || self.depth.get() > 0 {
+            return false;
+        }
+        self.input_len.set(data.len());
+        self.anchored.set(true);
+        self.allocated.set(0);
+        true
+    }
+    pub(crate) fn end_top_level<T>(
+        &self,
+        top_level: bool,
+        result: Result<T, CborError>,
+    ) -> Result<T, CborError> {
+        if !top_level {
+            return result;
+        }
+        self.anchored.set(false);
+        result.map_err(|error| match error {
+            CborError::Located { .. } => error,
+            error => CborError::Located {
+                offset: 0,
+                path: DecodePath::default(),
+                error: Box::new(error),
+            },
+        })
+    }
+
+    /// Attaches the position of the nested item starting at `data` to an error raised while decoding it.
+    pub fn locate<T, F>(
+        &self,
+        result: Result<T, CborError>,
+        data: &[u8],
+        segment: F,
+    ) -> Result<T, CborError>
+    where
+        F: FnOnce() -> PathSegment,
+    {
+        result.map_err(|error| match error {
+            CborError::Located {
+                offset,
+                mut path,
+                error,
+            } => {
+                path.0.insert(0, segment());
+                CborError::Located {
+                    offset,
+                    path,
+                    error,
+                }
+            }
+            error => CborError::Located {
+                offset: self.input_len.get().saturating_sub(data.len()),
+                path: DecodePath(vec![segment()]),
+                error: Box::new(error),
+            },
+        })
+    }
+
+    pub(crate) fn key_segment(&self, key: &[u8]) -> PathSegment {
+        match self.take_value(key) {
+            Ok((value, _)) => match value {
+                Value::Text(text) => PathSegment::Key(format!("{:?}", text)),
+                Value::U64(number) => PathSegment::Key(number.to_string()),
+                Value::I128(number) => PathSegment::Key(number.to_string()),
+                value => PathSegment::Key(format!("{:?}", value)),
+            },
+            Err(_) => PathSegment::Key("?".to_string()),
+        }
+    }
+}
@@ -1,23 +1,23 @@
-#[cfg(feature = "iana_numbers")]
-use half::f16;
-use nom::bytes::complete::take;
-#[cfg(feature = "iana_numbers")]
-use nom::number::complete::be_u16;
-use nom::number::complete::{be_f32, be_f64, be_u8};
+use nom::bytes::streaming::take;
+use nom::number::streaming::{be_f32, be_f64, be_u16, be_u8};
 
 use crate::context::Context;
 use crate::convert_slice::from_bytes;
-use crate::error::CborError;
-use crate::types::{IanaTag, Special, Type};
+use crate::error::{CborError, PathSegment};
+use crate::types::float::f16_to_f64;
+use crate::types::{IanaTag, Length, Special, Type};
 use crate::value::Value;
 use crate::ReducedSpecial;
-use nom::lib::std::collections::HashMap;
 use num_traits::Num;
 use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::convert::TryFrom;
 use std::hash::Hash;
 use std::rc::Rc;
 use std::sync::Arc;
 
+mod diagnostic;
 #[cfg(feature = "iana_bigint")]
 mod iana_bigint;
 #[cfg(feature = "iana_chrono")]
@@ -34,6 +34,19 @@ mod iana_regex;
 mod iana_std;
 #[cfg(feature = "iana_uuid")]
 mod iana_uuid;
+mod iter;
+mod limits;
+mod location;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod stream;
+mod strict;
+
+pub use iter::{ArrayIter, MapIter};
+pub use limits::DecodeLimits;
+#[cfg(feature = "serde")]
+pub use serde_impl::SerdeDeserializer;
+pub use stream::StreamDeserializer;
 
 #[allow(dead_code)]
 #[cfg(target_endian = "little")]
@@ -53,11 +66,27 @@ where
         data: &'de [u8],
         context: &Context,
     ) -> Result<(Self, &'de [u8]), CborError>;
+
+    /// Decodes a `Vec<Self>`, `u8` overrides it to accept byte strings and numbers to accept typed arrays.
+    fn deserialize_vec(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Vec<Self>, &'de [u8]), CborError> {
+        deserialize_nested_vec(deserializer, data, context)
+    }
 }
 
 pub struct Deserializer {
     //#[cfg(feature = "iana_string_ref")]
-//string_references: Vec<Vec<&str>>
+    //string_references: Vec<Vec<&str>>
+    limits: DecodeLimits,
+    depth: Cell<usize>,
+    allocated: Cell<usize>,
+    input_len: Cell<usize>,
+    /// Set while a top-level entry point fixed `input_len` to the start of its input.
+    anchored: Cell<bool>,
+    strict: bool,
 }
 impl Default for Deserializer {
     fn default() -> Self {
@@ -66,25 +95,42 @@ impl Default for Deserializer {
 }
 impl<'de> Deserializer {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            limits: DecodeLimits::default(),
+            depth: Cell::new(0),
+            allocated: Cell::new(0),
+            input_len: Cell::new(0),
+            anchored: Cell::new(false),
+            strict: false,
+        }
     }
 
-    fn take_type(
+    pub(crate) fn take_type(
         &self,
         data: &'de [u8],
         skip_tags: bool,
     ) -> Result<(Type, Remaining<'de>), CborError> {
         let mut remaining = data;
-        let tuple = loop {
-            let (ret, value) = be_u8(remaining)?;
-            remaining = ret;
-            let cur_type = Type::from_byte(value)?;
-            if cur_type.is_tag() && skip_tags {
-                continue;
-            }
-            break (cur_type, remaining);
-        };
-        Ok(tuple)
+        loop {
+            let (mut ret, value) = be_u8(remaining)?;
+            let cur_type = if value == 0b1110_0000 | 24 {
+                let (rem, simple) = be_u8(ret)?;
+                if simple < 32 {
+                    return Err(CborError::InvalidSimpleValue(simple));
+                }
+                ret = rem;
+                Type::Special(Special::Simple(simple))
+            } else {
+                Type::from_byte(value)?
+            };
+            if self.strict {
+                self.check_shortest_head(value, cur_type, ret)?;
+            }
+            match cur_type {
+                Type::Tag(int) if skip_tags => remaining = int.take_value(ret)?.0,
+                _ => break Ok((cur_type, ret)),
+            }
+        }
     }
 
     pub fn take_string(
@@ -106,6 +152,7 @@ impl<'de> Deserializer {
             _ => Err(CborError::ExpectText(cbor_type)),
         }?;
         if let Some(length) = o {
+            self.check_string_length(length)?;
             let (remaining, slice) = take(length)(data)?;
             let text = std::str::from_utf8(slice)?;
             Ok((text, remaining))
@@ -125,6 +172,7 @@ impl<'de> Deserializer {
             _ => Err(CborError::ExpectBytes(cbor_type)),
         }?;
         if let Some(length) = o {
+            self.check_string_length(length)?;
             let (remaining, slice) = take(length)(data)?;
             Ok((slice, remaining))
         } else {
@@ -132,6 +180,74 @@ impl<'de> Deserializer {
         }
     }
 
+    pub fn take_text_cow(
+        &self,
+        data: &'de [u8],
+        skip_tags: bool,
+    ) -> Result<(Cow<'de, str>, Remaining<'de>), CborError> {
+        let (cbor_type, mut remaining) = self.take_type(data, skip_tags)?;
+        match cbor_type {
+            Type::Text(Length::Indefinite) => {
+                let mut text = String::new();
+                loop {
+                    let (chunk_type, ret) = self.take_type(remaining, false)?;
+                    match chunk_type {
+                        Type::Special(Special::Break) => break Ok((Cow::Owned(text), ret)),
+                        Type::Text(Length::Indefinite) => {
+                            break Err(CborError::InvalidChunk(chunk_type))
+                        }
+                        Type::Text(_) => {
+                            let (chunk, ret) = self.take_text(remaining, false)?;
+                            self.check_string_length(text.len() + chunk.len())?;
+                            self.reserve_allocation(chunk.len())?;
+                            text.push_str(chunk);
+                            remaining = ret;
+                        }
+                        _ => break Err(CborError::InvalidChunk(chunk_type)),
+                    }
+                }
+            }
+            Type::Text(_) => self
+                .take_text(data, skip_tags)
+                .map(|(text, remaining)| (Cow::Borrowed(text), remaining)),
+            _ => Err(CborError::ExpectText(cbor_type)),
+        }
+    }
+
+    pub fn take_bytes_cow(
+        &self,
+        data: &'de [u8],
+        skip_tags: bool,
+    ) -> Result<(Cow<'de, [u8]>, Remaining<'de>), CborError> {
+        let (cbor_type, mut remaining) = self.take_type(data, skip_tags)?;
+        match cbor_type {
+            Type::Bytes(Length::Indefinite) => {
+                let mut bytes = Vec::new();
+                loop {
+                    let (chunk_type, ret) = self.take_type(remaining, false)?;
+                    match chunk_type {
+                        Type::Special(Special::Break) => break Ok((Cow::Owned(bytes), ret)),
+                        Type::Bytes(Length::Indefinite) => {
+                            break Err(CborError::InvalidChunk(chunk_type))
+                        }
+                        Type::Bytes(_) => {
+                            let (chunk, ret) = self.take_bytes(remaining, false)?;
+                            self.check_string_length(bytes.len() + chunk.len())?;
+                            self.reserve_allocation(chunk.len())?;
+                            bytes.extend_from_slice(chunk);
+                            remaining = ret;
+                        }
+                        _ => break Err(CborError::InvalidChunk(chunk_type)),
+                    }
+                }
+            }
+            Type::Bytes(_) => self
+                .take_bytes(data, skip_tags)
+                .map(|(bytes, remaining)| (Cow::Borrowed(bytes), remaining)),
+            _ => Err(CborError::ExpectBytes(cbor_type)),
+        }
+    }
+
     pub fn take_unsigned(
         &self,
         data: &'de [u8],
@@ -157,6 +273,80 @@ impl<'de> Deserializer {
         }?;
         Ok((o, data))
     }
+    /// Reads an unsigned integer or a positive bignum (tag 2) of up to 16 bytes.
+    pub fn take_u128(&self, data: &'de [u8]) -> Result<(u128, Remaining<'de>), CborError> {
+        match self.take_small_bignum(data, "u128")? {
+            Some((IanaTag::PositiveBigNum, value, remaining)) => Ok((value, remaining)),
+            Some((tag, _, _)) => Err(CborError::InvalidTag(tag, IanaTag::PositiveBigNum)),
+            None => self
+                .take_unsigned(data, true)
+                .map(|(value, remaining)| (value as u128, remaining)),
+        }
+    }
+    /// Reads an integer or a bignum (tag 2 or 3) fitting into an `i128`.
+    pub fn take_i128(&self, data: &'de [u8]) -> Result<(i128, Remaining<'de>), CborError> {
+        match self.take_small_bignum(data, "i128")? {
+            Some((_, value, _)) if value > i128::MAX as u128 => {
+                Err(CborError::BignumOutOfRange("i128"))
+            }
+            Some((IanaTag::PositiveBigNum, value, remaining)) => Ok((value as i128, remaining)),
+            Some((_, value, remaining)) => Ok((-1 - value as i128, remaining)),
+            None => self.take_negative(data, true),
+        }
+    }
+    /// Reads an unsigned integer and checks that it fits into `T`, `target` names `T` in errors.
+    pub fn take_unsigned_checked<T: TryFrom<u64>>(
+        &self,
+        data: &'de [u8],
+        target: &'static str,
+    ) -> Result<(T, Remaining<'de>), CborError> {
+        let (value, remaining) = match self.take_unsigned(data, true) {
+            Err(CborError::ExpectUnsigned(Type::NegativeInteger(_))) => {
+                let (value, _) = self.take_negative(data, true)?;
+                return Err(CborError::IntegerOutOfRange { value, target });
+            }
+            result => result?,
+        };
+        T::try_from(value)
+            .map(|value| (value, remaining))
+            .map_err(|_| CborError::IntegerOutOfRange {
+                value: value as i128,
+                target,
+            })
+    }
+    /// Reads an integer or bignum and checks that it fits into `T`, `target` names `T` in errors.
+    pub fn take_signed_checked<T: TryFrom<i128>>(
+        &self,
+        data: &'de [u8],
+        target: &'static str,
+    ) -> Result<(T, Remaining<'de>), CborError> {
+        let (value, remaining) = self.take_i128(data)?;
+        T::try_from(value)
+            .map(|value| (value, remaining))
+            .map_err(|_| CborError::IntegerOutOfRange { value, target })
+    }
+    fn take_small_bignum(
+        &self,
+        data: &'de [u8],
+        target: &'static str,
+    ) -> Result<Option<(IanaTag, u128, Remaining<'de>)>, CborError> {
+        if !matches!(self.take_type(data, false)?.0, Type::Tag(_)) {
+            return Ok(None);
+        }
+        let (tag, remaining) = self.take_tag(data)?;
+        if !matches!(tag, IanaTag::PositiveBigNum | IanaTag::NegativeBigNum) {
+            return Ok(None);
+        }
+        let (bytes, remaining) = self.take_bytes_cow(remaining, true)?;
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        let bytes = &bytes[start..];
+        if bytes.len() > 16 {
+            return Err(CborError::BignumOutOfRange(target));
+        }
+        let mut buffer = [0u8; 16];
+        buffer[16 - bytes.len()..].copy_from_slice(bytes);
+        Ok(Some((tag, u128::from_be_bytes(buffer), remaining)))
+    }
 
     pub fn take_bool(
         &self,
@@ -180,9 +370,8 @@ impl<'de> Deserializer {
         let (cbor_type, remaining) = self.take_type(data, skip_tags)?;
         let result = match cbor_type {
             Type::Special(special) => match special {
-                #[cfg(feature = "iana_numbers")]
                 Special::F16 => be_u16(remaining)
-                    .map(|v| (f16::from_bits(v.1).to_f64(), v.0))
+                    .map(|v| (f16_to_f64(v.1), v.0))
                     .map_err(CborError::from),
                 Special::F32 => be_f32(remaining)
                     .map(|v| (v.1 as f64, v.0))
@@ -295,8 +484,9 @@ impl<'de> Deserializer {
                 got: bytes.len(),
             });
         }
-        let le_array_and_aligned = tag.is_le_array() && cfg![target_endian = "little"];
-        let be_array_and_aligned = tag.is_be_array() && cfg![target_endian = "big"];
+        let aligned = bytes.as_ptr().align_offset(std::mem::align_of::<V>()) == 0;
+        let le_array_and_aligned = aligned && tag.is_le_array() && cfg![target_endian = "little"];
+        let be_array_and_aligned = aligned && tag.is_be_array() && cfg![target_endian = "big"];
         if le_array_and_aligned || be_array_and_aligned {
             let slice = from_bytes::<V>(bytes);
             Ok((Cow::Borrowed(slice), remaining))
@@ -332,23 +522,41 @@ impl<'de> Deserializer {
         }
     }
 
+    pub fn take_simple(
+        &self,
+        data: &'de [u8],
+        skip_tags: bool,
+    ) -> Result<(u8, Remaining<'de>), CborError> {
+        let (cbor_type, remaining) = self.take_type(data, skip_tags)?;
+        match cbor_type {
+            Type::Special(Special::Simple(val)) => Ok((val, remaining)),
+            _ => Err(CborError::ExpectSimple(cbor_type)),
+        }
+    }
+
     pub fn take_value(&self, data: &'de [u8]) -> Result<(Value<'de>, Remaining<'de>), CborError> {
+        let top_level = self.begin_top_level(data);
+        let result = self.take_value_nested(data);
+        self.end_top_level(top_level, result)
+    }
+
+    fn take_value_nested(
+        &self,
+        data: &'de [u8],
+    ) -> Result<(Value<'de>, Remaining<'de>), CborError> {
         let (cbor_type, remaining) = self.take_type(data, false)?;
         match cbor_type {
             Type::Special(s) => match s {
-                Special::F64 | Special::F32 => {
-                    let (number, remaining) = self.take_float(data, true)?;
-                    Ok((Value::F64(number), remaining))
-                }
-                #[cfg(feature = "iana_numbers")]
-                Special::F16 => {
+                Special::F64 | Special::F32 | Special::F16 => {
                     let (number, remaining) = self.take_float(data, true)?;
                     Ok((Value::F64(number), remaining))
                 }
                 Special::Bool(val) => Ok((Value::Bool(val), remaining)),
+                Special::Break if self.strict => Err(CborError::UnexpectedBreak),
                 Special::Break => Ok((Value::Special(ReducedSpecial::Break), remaining)),
                 Special::Null => Ok((Value::Special(ReducedSpecial::Null), remaining)),
                 Special::Undefined => Ok((Value::Special(ReducedSpecial::Undefined), remaining)),
+                Special::Simple(val) => Ok((Value::Simple(val), remaining)),
             },
             Type::NegativeInteger(_) => {
                 let (value, remaining) = self.take_negative(data, true)?;
@@ -360,89 +568,186 @@ impl<'de> Deserializer {
             }
             Type::Tag(_) => {
                 let (tag, remaining) = self.take_tag(data)?;
-                let (value, remaining) = self.take_value(remaining)?;
+                if self.strict {
+                    let (content_type, _) = self.take_type(remaining, false)?;
+                    self.check_tag_content(tag, content_type)?;
+                }
+                self.enter_nested(data)?;
+                let result = self.take_value_nested(remaining);
+                let result = self.locate(result, remaining, || PathSegment::Tag(tag.to_tag()));
+                self.leave_nested();
+                let (value, remaining) = result?;
                 Ok((Value::Tag(tag, Box::new(value)), remaining))
             }
             Type::Text(_) => {
-                let (string, remaining) = self.take_text(data, true)?;
+                let (string, remaining) = self.take_text_cow(data, true)?;
                 Ok((Value::Text(string), remaining))
             }
             Type::Bytes(_) => {
-                let (bytes, remaining) = self.take_bytes(data, true)?;
+                let (bytes, remaining) = self.take_bytes_cow(data, true)?;
                 Ok((Value::Bytes(bytes), remaining))
             }
             Type::Array(_) => {
-                let (length, remaining) = self.take_array_def(data, true)?;
-                let mut to_read = remaining;
-                let mut vec = Vec::with_capacity(length.unwrap_or(10));
-                if let Some(length) = length {
-                    for _ in 0..length {
-                        let (value, ret) = self.take_value(to_read)?;
-                        vec.push(value);
-                        to_read = ret;
-                    }
-                } else {
-                    loop {
-                        let (value, ret) = self.take_value(to_read)?;
-                        to_read = ret;
-
-                        let end = match value {
-                            Value::Special(s) => match s {
-                                ReducedSpecial::Break => true,
-                                _ => false,
-                            },
-                            _ => false,
-                        };
-                        if end {
-                            break;
-                        } else {
-                            vec.push(value);
+                self.enter_nested(data)?;
+                let result = self.take_array_value(data);
+                self.leave_nested();
+                result
+            }
+            Type::Map(_) => {
+                self.enter_nested(data)?;
+                let result = self.take_map_value(data);
+                self.leave_nested();
+                result
+            }
+        }
+    }
+
+    fn take_array_value(&self, data: &'de [u8]) -> Result<(Value<'de>, Remaining<'de>), CborError> {
+        let (length, remaining) = self.take_array_def(data, true)?;
+        let mut to_read = remaining;
+        let mut vec = Vec::with_capacity(self.reserve_collection::<Value>(length, to_read)?);
+        if let Some(length) = length {
+            for index in 0..length {
+                let result = self.take_value_nested(to_read);
+                let (value, ret) = self.locate(result, to_read, || PathSegment::Index(index))?;
+                vec.push(value);
+                to_read = ret;
+            }
+        } else {
+            loop {
+                let (end, ret) = self.check_break(to_read, false)?;
+                if end {
+                    to_read = ret;
+                    break;
+                }
+                self.grow_collection::<Value>(vec.len() + 1)?;
+                let result = self.take_value_nested(to_read);
+                let index = vec.len();
+                let (value, ret) = self.locate(result, to_read, || PathSegment::Index(index))?;
+                to_read = ret;
+                vec.push(value);
+            }
+        }
+        Ok((Value::Array(vec), to_read))
+    }
+
+    fn take_map_value(&self, data: &'de [u8]) -> Result<(Value<'de>, Remaining<'de>), CborError> {
+        let (length, remaining) = self.take_map_def(data, true)?;
+        let mut to_read = remaining;
+        let mut vec =
+            Vec::with_capacity(self.reserve_collection::<(Value, Value)>(length, to_read)?);
+        let mut keys = Vec::new();
+        loop {
+            if let Some(length) = length {
+                if vec.len() == length {
+                    break;
+                }
+            } else {
+                let (end, ret) = self.check_break(to_read, false)?;
+                if end {
+                    to_read = ret;
+                    break;
+                }
+                self.grow_collection::<(Value, Value)>(vec.len() + 1)?;
+            }
+            let result = self.take_value_nested(to_read);
+            let index = vec.len();
+            let (key, ret) = self.locate(result, to_read, || PathSegment::Index(index))?;
+            let key_bytes = &to_read[..to_read.len() - ret.len()];
+            if self.strict {
+                keys.push(key_bytes);
+            }
+            to_read = ret;
+            let result = self.take_value_nested(to_read);
+            let (value, ret) = self.locate(result, to_read, || self.key_segment(key_bytes))?;
+            to_read = ret;
+            vec.push((key, value));
+        }
+        self.check_unique_keys(keys)?;
+        Ok((Value::Map(vec), to_read))
+    }
+    pub fn skip_key_value(&self, data: &'de [u8]) -> Result<Remaining<'de>, CborError> {
+        let remaining = self.skip_value(data)?;
+        self.skip_value(remaining)
+    }
+
+    /// Skips one item including all nested items by only walking the headers, nothing is allocated.
+    pub fn skip_value(&self, data: &'de [u8]) -> Result<Remaining<'de>, CborError> {
+        let (cbor_type, remaining) = self.take_type(data, false)?;
+        match cbor_type {
+            Type::UnsignedInteger(int) => int.take_value(remaining).map(|v| v.0),
+            Type::NegativeInteger(int) => int.take_value(remaining).map(|v| v.0),
+            Type::Tag(int) => {
+                let (remaining, _) = int.take_value(remaining)?;
+                self.skip_nested(data, remaining)
+            }
+            Type::Bytes(length) | Type::Text(length) => {
+                let (mut remaining, o) = length.take_length_to_read(remaining)?;
+                if let Some(length) = o {
+                    self.check_string_length(length)?;
+                    let (remaining, _) = take(length)(remaining)?;
+                    return Ok(remaining);
+                }
+                let mut total: usize = 0;
+                loop {
+                    let (chunk_type, ret) = self.take_type(remaining, false)?;
+                    let length = match (chunk_type, cbor_type) {
+                        (Type::Special(Special::Break), _) => break Ok(ret),
+                        (Type::Bytes(length), Type::Bytes(_))
+                        | (Type::Text(length), Type::Text(_))
+                            if length != Length::Indefinite =>
+                        {
+                            length
                         }
-                    }
+                        _ => break Err(CborError::InvalidChunk(chunk_type)),
+                    };
+                    let (ret, o) = length.take_length_to_read(ret)?;
+                    let length = o.unwrap_or(0);
+                    total = total.saturating_add(length);
+                    self.check_string_length(total)?;
+                    remaining = take(length)(ret)?.0;
                 }
-                Ok((Value::Array(vec), to_read))
             }
-            Type::Map(_) => {
-                let (length, remaining) = self.take_map_def(data, true)?;
-                let mut to_read = remaining;
-                let mut vec = Vec::with_capacity(length.unwrap_or(10));
-                if let Some(length) = length {
-                    for _ in 0..length {
-                        let (key, ret) = self.take_value(to_read)?;
-                        to_read = ret;
-                        let (value, ret) = self.take_value(to_read)?;
-                        to_read = ret;
-                        vec.push((key, value));
+            Type::Array(length) | Type::Map(length) => {
+                let factor = if let Type::Map(_) = cbor_type { 2 } else { 1 };
+                let (mut remaining, o) = length.take_length_to_read(remaining)?;
+                if let Some(length) = o {
+                    self.check_collection_length(length)?;
+                    for _ in 0..length.saturating_mul(factor) {
+                        remaining = self.skip_nested(data, remaining)?;
                     }
-                } else {
-                    loop {
-                        let (key, ret) = self.take_value(to_read)?;
-                        to_read = ret;
-
-                        let end = match key {
-                            Value::Special(s) => match s {
-                                ReducedSpecial::Break => true,
-                                _ => false,
-                            },
-                            _ => false,
-                        };
-                        if end {
-                            break;
-                        } else {
-                            let (value, ret) = self.take_value(to_read)?;
-                            to_read = ret;
-                            vec.push((key, value));
-                        }
+                    return Ok(remaining);
+                }
+                let mut items = 0;
+                loop {
+                    let (is_break, ret) = self.check_break(remaining, false)?;
+                    if is_break {
+                        break Ok(ret);
                     }
+                    items += 1;
+                    self.check_collection_length(items / factor)?;
+                    remaining = self.skip_nested(data, remaining)?;
                 }
-                Ok((Value::Map(vec), to_read))
+            }
+            Type::Special(special) => {
+                let size: usize = match special {
+                    Special::F16 => 2,
+                    Special::F32 => 4,
+                    Special::F64 => 8,
+                    Special::Break if self.strict => return Err(CborError::UnexpectedBreak),
+                    _ => 0,
+                };
+                let (remaining, _) = take(size)(remaining)?;
+                Ok(remaining)
             }
         }
     }
-    pub fn skip_key_value(&self, data: &'de [u8]) -> Result<Remaining<'de>, CborError> {
-        let (_, remaining) = self.take_value(data)?;
-        let (_, remaining) = self.take_value(remaining)?;
-        Ok(remaining)
+
+    fn skip_nested(&self, data: &'de [u8], item: &'de [u8]) -> Result<Remaining<'de>, CborError> {
+        self.enter_nested(data)?;
+        let result = self.skip_value(item);
+        self.leave_nested();
+        result
     }
 
     pub fn found_contains_any(&self, haystack: &[u64], needle: &[u64]) -> bool {
@@ -478,9 +783,31 @@ impl<'de> Deserialize<'de> for String {
         data: &'de [u8],
         _context: &Context,
     ) -> Result<(Self, &'de [u8]), CborError> {
-        deserializer
-            .take_text(data, true)
-            .map(|t| (String::from(t.0), t.1))
+        let (text, remaining) = deserializer.take_text_cow(data, true)?;
+        if let Cow::Borrowed(text) = text {
+            deserializer.reserve_allocation(text.len())?;
+        }
+        Ok((text.into_owned(), remaining))
+    }
+}
+
+impl<'de> Deserialize<'de> for Cow<'de, str> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_text_cow(data, true)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cow<'de, [u8]> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_bytes_cow(data, true)
     }
 }
 
@@ -490,29 +817,51 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Vec<T> {
         data: &'de [u8],
         context: &Context,
     ) -> Result<(Self, &'de [u8]), CborError> {
-        let (o, mut remaining) = deserializer.take_array_def(data, true)?;
-        let mut vec = Vec::with_capacity(o.unwrap_or(100));
+        T::deserialize_vec(deserializer, data, context)
+    }
+}
 
-        let mut visited_elemnents = 0;
-        loop {
-            if let Some(max) = o {
-                if visited_elemnents == max {
-                    break;
-                }
-            } else {
-                let (is_break, ret) = deserializer.check_break(remaining, true)?;
-                if is_break {
-                    remaining = ret;
-                    break;
-                }
+fn deserialize_nested_vec<'de, T: Deserialize<'de>>(
+    deserializer: &mut Deserializer,
+    data: &'de [u8],
+    context: &Context,
+) -> Result<(Vec<T>, &'de [u8]), CborError> {
+    deserializer.enter_nested(data)?;
+    let result = deserialize_vec(deserializer, data, context);
+    deserializer.leave_nested();
+    result
+}
+
+fn deserialize_vec<'de, T: Deserialize<'de>>(
+    deserializer: &mut Deserializer,
+    data: &'de [u8],
+    context: &Context,
+) -> Result<(Vec<T>, &'de [u8]), CborError> {
+    let (o, mut remaining) = deserializer.take_array_def(data, true)?;
+    let mut vec = Vec::with_capacity(deserializer.reserve_collection::<T>(o, remaining)?);
+
+    let mut visited_elemnents = 0;
+    loop {
+        if let Some(max) = o {
+            if visited_elemnents == max {
+                break;
+            }
+        } else {
+            let (is_break, ret) = deserializer.check_break(remaining, true)?;
+            if is_break {
+                remaining = ret;
+                break;
             }
-            let (value, ret) = T::deserialize(deserializer, remaining, context)?;
-            remaining = ret;
-            vec.push(value);
-            visited_elemnents += 1;
+            deserializer.grow_collection::<T>(visited_elemnents + 1)?;
         }
-        Ok((vec, remaining))
+        let result = T::deserialize(deserializer, remaining, context);
+        let (value, ret) =
+            deserializer.locate(result, remaining, || PathSegment::Index(visited_elemnents))?;
+        remaining = ret;
+        vec.push(value);
+        visited_elemnents += 1;
     }
+    Ok((vec, remaining))
 }
 
 macro_rules! impl_pos_number {
@@ -523,12 +872,22 @@ macro_rules! impl_pos_number {
                 data: &'de [u8],
                 _context: &Context,
             ) -> Result<(Self, &'de [u8]), CborError> {
-                deserializer
-                    .take_unsigned(data, true)
-                    .map(|(v, remaining)| (v as $number, remaining))
+                deserializer.take_unsigned_checked(data, stringify!($number))
             }
         }
     };
+    ($number:ty, $take_array:ident, $($tag:ident)|+) => {
+        impl<'de> Deserialize<'de> for $number {
+            fn deserialize(
+                deserializer: &mut Deserializer,
+                data: &'de [u8],
+                _context: &Context,
+            ) -> Result<(Self, &'de [u8]), CborError> {
+                deserializer.take_unsigned_checked(data, stringify!($number))
+            }
+            impl_typed_array!($take_array, $($tag)|+);
+        }
+    };
 }
 macro_rules! impl_neg_number {
     ($number:ty) => {
@@ -538,26 +897,101 @@ macro_rules! impl_neg_number {
                 data: &'de [u8],
                 _context: &Context,
             ) -> Result<(Self, &'de [u8]), CborError> {
-                deserializer
-                    .take_negative(data, true)
-                    .map(|(v, remaining)| (v as $number, remaining))
+                deserializer.take_signed_checked(data, stringify!($number))
+            }
+        }
+    };
+    ($number:ty, $take_array:ident, $($tag:ident)|+) => {
+        impl<'de> Deserialize<'de> for $number {
+            fn deserialize(
+                deserializer: &mut Deserializer,
+                data: &'de [u8],
+                _context: &Context,
+            ) -> Result<(Self, &'de [u8]), CborError> {
+                deserializer.take_signed_checked(data, stringify!($number))
             }
+            impl_typed_array!($take_array, $($tag)|+);
+        }
+    };
+}
+/// Overrides `deserialize_vec` to also accept typed arrays with `iana_std`.
+macro_rules! impl_typed_array {
+    ($take_array:ident, $($tag:ident)|+) => {
+        #[cfg(feature = "iana_std")]
+        fn deserialize_vec(
+            deserializer: &mut Deserializer,
+            data: &'de [u8],
+            context: &Context,
+        ) -> Result<(Vec<Self>, &'de [u8]), CborError> {
+            let typed_array = match deserializer.take_type(data, false)?.0 {
+                Type::Tag(_) => matches!(deserializer.take_tag(data)?.0, $(IanaTag::$tag)|+),
+                _ => false,
+            };
+            if typed_array {
+                let (array, remaining) = deserializer.$take_array(data)?;
+                deserializer.reserve_allocation(std::mem::size_of_val(array.as_ref()))?;
+                return Ok((array.into_owned(), remaining));
+            }
+            deserialize_nested_vec(deserializer, data, context)
         }
     };
 }
 
 impl_pos_number!(usize);
-impl_pos_number!(u8);
-impl_pos_number!(u16);
-impl_pos_number!(u32);
-impl_pos_number!(u64);
+impl_pos_number!(u16, take_u16_array, Uint16BeArray | Uint16LeArray);
+impl_pos_number!(u32, take_u32_array, Uint32BeArray | Uint32LeArray);
+impl_pos_number!(u64, take_u64_array, Uint64BeArray | Uint64LeArray);
 
 impl_neg_number!(isize);
-impl_neg_number!(i8);
-impl_neg_number!(i16);
-impl_neg_number!(i32);
-impl_neg_number!(i64);
-impl_neg_number!(i128);
+impl_neg_number!(i8, take_i8_array, Sint8Array);
+impl_neg_number!(i16, take_i16_array, Sint16BeArray | Sint16LeArray);
+impl_neg_number!(i32, take_i32_array, Sint32BeArray | Sint32LeArray);
+impl_neg_number!(i64, take_i64_array, Sint64BeArray | Sint64LeArray);
+
+impl<'de> Deserialize<'de> for u8 {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_unsigned_checked(data, "u8")
+    }
+
+    fn deserialize_vec(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Vec<Self>, &'de [u8]), CborError> {
+        if let Type::Array(_) = deserializer.take_type(data, true)?.0 {
+            return deserialize_nested_vec(deserializer, data, context);
+        }
+        let (bytes, remaining) = deserializer.take_bytes_cow(data, true)?;
+        if let Cow::Borrowed(bytes) = bytes {
+            deserializer.reserve_allocation(bytes.len())?;
+        }
+        Ok((bytes.into_owned(), remaining))
+    }
+}
+
+impl<'de> Deserialize<'de> for u128 {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_u128(data)
+    }
+}
+
+impl<'de> Deserialize<'de> for i128 {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_i128(data)
+    }
+}
 
 impl<'de, T: Deserialize<'de>> Deserialize<'de> for Option<T> {
     fn deserialize(
@@ -565,11 +999,11 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Option<T> {
         data: &'de [u8],
         context: &Context,
     ) -> Result<(Self, &'de [u8]), CborError> {
-        let result = T::deserialize(deserializer, data, context);
-        if let Ok(res) = result {
-            Ok((Some(res.0), res.1))
+        let (is_null, remaining) = deserializer.check_null_or_undefined(data, true)?;
+        if is_null {
+            Ok((None, remaining))
         } else {
-            Ok((None, data))
+            T::deserialize(deserializer, data, context).map(|(t, remaining)| (Some(t), remaining))
         }
     }
 }
@@ -579,47 +1013,86 @@ impl<'de, K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>> Deserialize<'de>
         data: &'de [u8],
         context: &Context,
     ) -> Result<(Self, &'de [u8]), CborError> {
-        let (length, remaining) = deserializer.take_map_def(data, true)?;
-        let mut to_read = remaining;
-        let mut map = if let Some(length) = length {
-            HashMap::with_capacity(length)
-        } else {
-            HashMap::new()
-        };
+        deserializer.enter_nested(data)?;
+        let result = deserialize_map(
+            deserializer,
+            data,
+            context,
+            HashMap::with_capacity,
+            |map, k, v| map.insert(k, v).is_some(),
+        );
+        deserializer.leave_nested();
+        result
+    }
+}
 
-        if let Some(length) = length {
-            for _ in 0..length {
-                let (key, ret) = K::deserialize(deserializer, to_read, context)?;
-                to_read = ret;
+impl<'de, K: Deserialize<'de> + Ord, V: Deserialize<'de>> Deserialize<'de> for BTreeMap<K, V> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.enter_nested(data)?;
+        let result = deserialize_map(
+            deserializer,
+            data,
+            context,
+            |_| BTreeMap::new(),
+            |map, k, v| map.insert(k, v).is_some(),
+        );
+        deserializer.leave_nested();
+        result
+    }
+}
 
-                let (value, ret) = V::deserialize(deserializer, to_read, context)?;
-                to_read = ret;
-                map.insert(key, value);
+/// `insert` returns whether the key was already present.
+fn deserialize_map<'de, K, V, M, C, I>(
+    deserializer: &mut Deserializer,
+    data: &'de [u8],
+    context: &Context,
+    create: C,
+    mut insert: I,
+) -> Result<(M, &'de [u8]), CborError>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: FnOnce(usize) -> M,
+    I: FnMut(&mut M, K, V) -> bool,
+{
+    let (length, remaining) = deserializer.take_map_def(data, true)?;
+    let mut to_read = remaining;
+    let mut map = create(deserializer.reserve_collection::<(K, V)>(length, to_read)?);
+
+    let mut visited_elements = 0;
+    loop {
+        if let Some(length) = length {
+            if visited_elements == length {
+                break;
             }
         } else {
-            loop {
-                let end = if let Ok((special, _)) = deserializer.take_reduced_special(to_read) {
-                    match special {
-                        ReducedSpecial::Break => true,
-                        _ => false,
-                    }
-                } else {
-                    false
-                };
-                if end {
-                    break;
-                }
-
-                let (key, ret) = K::deserialize(deserializer, to_read, context)?;
+            let (end, ret) = deserializer.check_break(to_read, false)?;
+            if end {
                 to_read = ret;
-
-                let (value, ret) = V::deserialize(deserializer, to_read, context)?;
-                to_read = ret;
-                map.insert(key, value);
+                break;
             }
+            deserializer.grow_collection::<(K, V)>(visited_elements + 1)?;
         }
-        Ok((map, to_read))
+        let result = K::deserialize(deserializer, to_read, context);
+        let (key, ret) =
+            deserializer.locate(result, to_read, || PathSegment::Index(visited_elements))?;
+        let key_bytes = &to_read[..to_read.len() - ret.len()];
+        to_read = ret;
+
+        let result = V::deserialize(deserializer, to_read, context);
+        let (value, ret) =
+            deserializer.locate(result, to_read, || deserializer.key_segment(key_bytes))?;
+        to_read = ret;
+        if insert(&mut map, key, value) && deserializer.is_strict() {
+            return Err(CborError::DuplicateMapKey(key_bytes.to_vec()));
+        }
+        visited_elements += 1;
     }
+    Ok((map, to_read))
 }
 
 impl<'de> Deserialize<'de> for bool {
@@ -644,6 +1117,7 @@ impl<'de> Deserialize<'de> for f32 {
             .take_float(data, true)
             .map(|(v, remaining)| (v as f32, remaining))
     }
+    impl_typed_array!(take_f32_array, F32BeArray | F32LeArray);
 }
 
 impl<'de> Deserialize<'de> for f64 {
@@ -656,6 +1130,7 @@ impl<'de> Deserialize<'de> for f64 {
             .take_float(data, true)
             .map(|(v, remaining)| (v as f64, remaining))
     }
+    impl_typed_array!(take_f64_array, F64BeArray | F64LeArray);
 }
 
 impl<'de, T: Deserialize<'de>> Deserialize<'de> for Arc<T> {
@@ -676,3 +1151,204 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Rc<T> {
         T::deserialize(deserializer, data, context).map(|t| (Rc::new(t.0), t.1))
     }
 }
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Box<T> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        T::deserialize(deserializer, data, context).map(|t| (Box::new(t.0), t.1))
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Clone> Deserialize<'de> for Cow<'de, T> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        T::deserialize(deserializer, data, context).map(|t| (Cow::Owned(t.0), t.1))
+    }
+}
+
+impl<'de> Deserialize<'de> for char {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        let (text, remaining) = deserializer.take_text_cow(data, true)?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok((c, remaining)),
+            _ => Err(CborError::ExpectChar(text.into_owned())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for () {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        let (cbor_type, remaining) = deserializer.take_type(data, true)?;
+        match cbor_type {
+            Type::Special(Special::Null) => Ok(((), remaining)),
+            Type::Special(special) => Err(CborError::ExpectNull(special)),
+            _ => Err(CborError::ExpectSpecial(cbor_type)),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const N: usize> Deserialize<'de> for [T; N] {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        let (vec, remaining) = T::deserialize_vec(deserializer, data, context)?;
+        let array = <[T; N]>::try_from(vec).map_err(|vec| CborError::InvalidArrayLength {
+            expected: FixedLength::<N>::EXPECTED,
+            got: vec.len(),
+        })?;
+        Ok((array, remaining))
+    }
+}
+
+struct FixedLength<const N: usize>;
+
+impl<const N: usize> FixedLength<N> {
+    const EXPECTED: &'static [usize] = &[N];
+}
+
+/// Reads the head of a tuple, returns whether it is indefinite.
+fn take_tuple_def<'de>(
+    deserializer: &Deserializer,
+    data: &'de [u8],
+    expected: &'static [usize],
+) -> Result<(bool, Remaining<'de>), CborError> {
+    let (length, remaining) = deserializer.take_array_def(data, true)?;
+    match length {
+        Some(got) if got != expected[0] => Err(CborError::InvalidArrayLength { expected, got }),
+        length => Ok((length.is_none(), remaining)),
+    }
+}
+
+fn take_tuple_element<'de, T: Deserialize<'de>>(
+    deserializer: &mut Deserializer,
+    data: &'de [u8],
+    context: &Context,
+    indefinite: bool,
+    index: usize,
+    expected: &'static [usize],
+) -> Result<(T, Remaining<'de>), CborError> {
+    if indefinite && deserializer.check_break(data, true)?.0 {
+        return Err(CborError::InvalidArrayLength {
+            expected,
+            got: index,
+        });
+    }
+    let result = T::deserialize(deserializer, data, context);
+    deserializer.locate(result, data, || PathSegment::Index(index))
+}
+
+fn take_tuple_end<'de>(
+    deserializer: &Deserializer,
+    data: &'de [u8],
+    indefinite: bool,
+    expected: &'static [usize],
+) -> Result<Remaining<'de>, CborError> {
+    if !indefinite {
+        return Ok(data);
+    }
+    let mut remaining = data;
+    let mut got = expected[0];
+    loop {
+        let (is_break, ret) = deserializer.check_break(remaining, true)?;
+        if is_break {
+            break;
+        }
+        remaining = deserializer.skip_value(ret)?;
+        got += 1;
+    }
+    if got == expected[0] {
+        Ok(deserializer.check_break(remaining, true)?.1)
+    } else {
+        Err(CborError::InvalidArrayLength { expected, got })
+    }
+}
+
+macro_rules! impl_tuple {
+    ($length:expr => $($name:ident $value:ident $index:tt),+) => {
+        impl<'de, $($name: Deserialize<'de>),+> Deserialize<'de> for ($($name,)+) {
+            fn deserialize(
+                deserializer: &mut Deserializer,
+                data: &'de [u8],
+                context: &Context,
+            ) -> Result<(Self, &'de [u8]), CborError> {
+                deserializer.enter_nested(data)?;
+                let result = (|| -> Result<(Self, &'de [u8]), CborError> {
+                    let (indefinite, mut remaining) = take_tuple_def(deserializer, data, &[$length])?;
+                    $(
+                        let ($value, ret) = take_tuple_element::<$name>(
+                            deserializer, remaining, context, indefinite, $index, &[$length],
+                        )?;
+                        remaining = ret;
+                    )+
+                    let remaining = take_tuple_end(deserializer, remaining, indefinite, &[$length])?;
+                    Ok((($($value,)+), remaining))
+                })();
+                deserializer.leave_nested();
+                result
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => A a 0);
+impl_tuple!(2 => A a 0, B b 1);
+impl_tuple!(3 => A a 0, B b 1, C c 2);
+impl_tuple!(4 => A a 0, B b 1, C c 2, D d 3);
+impl_tuple!(5 => A a 0, B b 1, C c 2, D d 3, E e 4);
+impl_tuple!(6 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5);
+impl_tuple!(7 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6);
+impl_tuple!(8 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7);
+impl_tuple!(9 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8);
+impl_tuple!(10 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9);
+impl_tuple!(11 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10);
+impl_tuple!(12 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11);
+
+impl<'de, T: Deserialize<'de> + Eq + Hash> Deserialize<'de> for HashSet<T> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        Vec::<T>::deserialize(deserializer, data, context)
+            .map(|(vec, remaining)| (vec.into_iter().collect(), remaining))
+    }
+}
+
+impl<'de, T: Deserialize<'de> + Ord> Deserialize<'de> for BTreeSet<T> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        Vec::<T>::deserialize(deserializer, data, context)
+            .map(|(vec, remaining)| (vec.into_iter().collect(), remaining))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VecDeque<T> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        Vec::<T>::deserialize(deserializer, data, context)
+            .map(|(vec, remaining)| (vec.into(), remaining))
+    }
+}
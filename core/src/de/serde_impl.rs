@@ -0,0 +1,365 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Unexpected, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::de::{Deserializer, Remaining};
+use crate::error::{CborError, PathSegment};
+use crate::types::{IanaTag, Special, Type};
+
+/// Reads serde's data model from cbor, the counterpart of `ser::Serializer for &mut Serializer`.
+///
+/// Strings and bytes are borrowed from the input unless they are chunked.
+/// Tags are skipped, except bignums (tag 2 and 3) which are read as `u128` and `i128`.
+pub struct SerdeDeserializer<'a, 'de> {
+    deserializer: &'a mut Deserializer,
+    data: &'de [u8],
+}
+
+impl<'a, 'de> SerdeDeserializer<'a, 'de> {
+    pub fn new(deserializer: &'a mut Deserializer, data: &'de [u8]) -> Self {
+        Self { deserializer, data }
+    }
+    /// The input following the items read so far.
+    pub fn remaining(&self) -> Remaining<'de> {
+        self.data
+    }
+
+    fn nested<F, T>(&mut self, read: F) -> Result<T, CborError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, CborError>,
+    {
+        self.deserializer.enter_nested(self.data)?;
+        let result = read(self);
+        self.deserializer.leave_nested();
+        result
+    }
+    fn visit_array<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, CborError> {
+        let (length, remaining) = self.deserializer.take_array_def(self.data, true)?;
+        if let Some(length) = length {
+            self.deserializer.check_collection_length(length)?;
+        }
+        self.nested(|de| {
+            de.data = remaining;
+            let mut items = Items::new(de, length);
+            let value = visitor.visit_seq(&mut items)?;
+            items.end()?;
+            Ok(value)
+        })
+    }
+    fn visit_map<V: Visitor<'de>>(&mut self, visitor: V) -> Result<V::Value, CborError> {
+        let (length, remaining) = self.deserializer.take_map_def(self.data, true)?;
+        if let Some(length) = length {
+            self.deserializer.check_collection_length(length)?;
+        }
+        self.nested(|de| {
+            de.data = remaining;
+            let mut items = Items::new(de, length);
+            let value = visitor.visit_map(&mut items)?;
+            items.end()?;
+            Ok(value)
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut SerdeDeserializer<'_, 'de> {
+    type Error = CborError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, CborError> {
+        let (cbor_type, remaining) = self.deserializer.take_type(self.data, false)?;
+        match cbor_type {
+            Type::UnsignedInteger(_) => {
+                let (value, remaining) = self.deserializer.take_unsigned(self.data, false)?;
+                self.data = remaining;
+                visitor.visit_u64(value)
+            }
+            Type::NegativeInteger(_) => {
+                let (value, remaining) = self.deserializer.take_negative(self.data, false)?;
+                self.data = remaining;
+                match i64::try_from(value) {
+                    Ok(value) => visitor.visit_i64(value),
+                    Err(_) => visitor.visit_i128(value),
+                }
+            }
+            Type::Bytes(_) => {
+                let (bytes, remaining) = self.deserializer.take_bytes_cow(self.data, false)?;
+                self.data = remaining;
+                match bytes {
+                    Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+                    Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+                }
+            }
+            Type::Text(_) => {
+                let (text, remaining) = self.deserializer.take_text_cow(self.data, false)?;
+                self.data = remaining;
+                match text {
+                    Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+                    Cow::Owned(text) => visitor.visit_string(text),
+                }
+            }
+            Type::Array(_) => self.visit_array(visitor),
+            Type::Map(_) => self.visit_map(visitor),
+            Type::Tag(_) => match self.deserializer.take_tag(self.data)? {
+                (IanaTag::PositiveBigNum, _) => self.deserialize_u128(visitor),
+                (IanaTag::NegativeBigNum, _) => self.deserialize_i128(visitor),
+                (tag, remaining) => {
+                    let data = self.data;
+                    self.data = remaining;
+                    let result = self.deserialize_any(visitor);
+                    self.deserializer
+                        .locate(result, data, || PathSegment::Tag(tag.to_tag()))
+                }
+            },
+            Type::Special(special) => match special {
+                Special::Bool(value) => {
+                    self.data = remaining;
+                    visitor.visit_bool(value)
+                }
+                Special::Null | Special::Undefined => {
+                    self.data = remaining;
+                    visitor.visit_unit()
+                }
+                Special::F16 | Special::F32 | Special::F64 => {
+                    let (value, remaining) = self.deserializer.take_float(self.data, false)?;
+                    self.data = remaining;
+                    visitor.visit_f64(value)
+                }
+                Special::Break => Err(CborError::UnexpectedBreak),
+                Special::Simple(value) => Err(de::Error::invalid_type(
+                    Unexpected::Unsigned(value as u64),
+                    &visitor,
+                )),
+            },
+        }
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, CborError> {
+        let (value, remaining) = self.deserializer.take_i128(self.data)?;
+        self.data = remaining;
+        visitor.visit_i128(value)
+    }
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, CborError> {
+        let (value, remaining) = self.deserializer.take_u128(self.data)?;
+        self.data = remaining;
+        visitor.visit_u128(value)
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, CborError> {
+        let (is_null, remaining) = self.deserializer.check_null_or_undefined(self.data, true)?;
+        if is_null {
+            self.data = remaining;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, CborError> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, CborError> {
+        let (cbor_type, _) = self.deserializer.take_type(self.data, true)?;
+        match cbor_type {
+            Type::Text(_) => {
+                let (text, remaining) = self.deserializer.take_text_cow(self.data, true)?;
+                self.data = remaining;
+                visitor.visit_enum(IntoDeserializer::<CborError>::into_deserializer(text))
+            }
+            Type::Map(_) => {
+                let (length, remaining) = self.deserializer.take_map_def(self.data, true)?;
+                if let Some(length) = length.filter(|length| *length != 1) {
+                    return Err(de::Error::invalid_length(length, &"a map with one entry"));
+                }
+                self.nested(|de| {
+                    de.data = remaining;
+                    let value = visitor.visit_enum(&mut *de)?;
+                    if length.is_none() {
+                        let (is_break, remaining) = de.deserializer.check_break(de.data, false)?;
+                        if !is_break {
+                            return Err(de::Error::invalid_length(2, &"a map with one entry"));
+                        }
+                        de.data = remaining;
+                    }
+                    Ok(value)
+                })
+            }
+            _ => Err(de::Error::invalid_type(
+                Unexpected::Other("cbor item"),
+                &"a text or a map with one entry",
+            )),
+        }
+    }
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, CborError> {
+        self.data = self.deserializer.skip_value(self.data)?;
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// Hands out the items of an array or the entries of a map.
+struct Items<'b, 'a, 'de> {
+    de: &'b mut SerdeDeserializer<'a, 'de>,
+    /// `None` for indefinite lengths.
+    remaining: Option<usize>,
+    ended: bool,
+    index: usize,
+    key: &'de [u8],
+    /// Encoded keys seen so far, only tracked in strict mode.
+    keys: HashSet<&'de [u8]>,
+}
+
+impl<'b, 'a, 'de> Items<'b, 'a, 'de> {
+    fn new(de: &'b mut SerdeDeserializer<'a, 'de>, length: Option<usize>) -> Self {
+        Self {
+            de,
+            remaining: length,
+            ended: false,
+            index: 0,
+            key: &[],
+            keys: HashSet::new(),
+        }
+    }
+    fn has_next(&mut self) -> Result<bool, CborError> {
+        match &mut self.remaining {
+            _ if self.ended => Ok(false),
+            Some(0) => Ok(false),
+            Some(remaining) => {
+                *remaining -= 1;
+                Ok(true)
+            }
+            None => {
+                let (is_break, remaining) =
+                    self.de.deserializer.check_break(self.de.data, false)?;
+                if is_break {
+                    self.de.data = remaining;
+                    self.ended = true;
+                } else {
+                    self.de
+                        .deserializer
+                        .check_collection_length(self.index + 1)?;
+                }
+                Ok(!is_break)
+            }
+        }
+    }
+    fn next<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, CborError> {
+        let data = self.de.data;
+        let index = self.index;
+        let result = seed.deserialize(&mut *self.de);
+        self.de
+            .deserializer
+            .locate(result, data, || PathSegment::Index(index))
+    }
+    /// Fails if the visitor stopped before the end of the container.
+    fn end(mut self) -> Result<(), CborError> {
+        if self.has_next()? {
+            return Err(de::Error::invalid_length(
+                self.index,
+                &"fewer items in the container",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for Items<'_, '_, 'de> {
+    type Error = CborError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, CborError> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+        let value = self.next(seed)?;
+        self.index += 1;
+        Ok(Some(value))
+    }
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Items<'_, '_, 'de> {
+    type Error = CborError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, CborError> {
+        if !self.has_next()? {
+            return Ok(None);
+        }
+        let data = self.de.data;
+        let key = self.next(seed)?;
+        self.key = &data[..data.len() - self.de.data.len()];
+        if self.de.deserializer.is_strict() && !self.keys.insert(self.key) {
+            return Err(CborError::DuplicateMapKey(self.key.to_vec()));
+        }
+        Ok(Some(key))
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, CborError> {
+        let data = self.de.data;
+        let result = seed.deserialize(&mut *self.de);
+        let key = self.key;
+        self.index += 1;
+        let deserializer = &self.de.deserializer;
+        deserializer.locate(result, data, || deserializer.key_segment(key))
+    }
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut SerdeDeserializer<'_, 'de> {
+    type Error = CborError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), CborError> {
+        let value = seed.deserialize(&mut *self)?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut SerdeDeserializer<'_, 'de> {
+    type Error = CborError;
+
+    fn unit_variant(self) -> Result<(), CborError> {
+        de::Deserialize::deserialize(self)
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, CborError> {
+        seed.deserialize(self)
+    }
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, CborError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, CborError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::{ErrorKind, Read};
+
+use crate::context::Context;
+use crate::de::{DecodeLimits, Deserialize, Deserializer};
+use crate::error::CborError;
+use crate::value::Value;
+
+const DEFAULT_CHUNK_SIZE: usize = 8 * 1024;
+
+/// Reads consecutive cbor items from an `io::Read`.
+///
+/// Bytes are buffered until one complete item is available, the item is then handed out
+/// and dropped from the buffer on the next call.
+pub struct StreamDeserializer<R: Read> {
+    reader: R,
+    deserializer: Deserializer,
+    context: Context,
+    buffer: Vec<u8>,
+    consumed: usize,
+    chunk_size: usize,
+    failed: bool,
+}
+
+impl<R: Read> StreamDeserializer<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_context(reader, Context::new())
+    }
+    pub fn with_context(reader: R, context: Context) -> Self {
+        Self {
+            reader,
+            deserializer: Deserializer::new(),
+            context,
+            buffer: Vec::new(),
+            consumed: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            failed: false,
+        }
+    }
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.deserializer.set_limits(limits);
+        self
+    }
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Returns the raw bytes of the next complete item or `None` at the end of the stream.
+    pub fn next_item(&mut self) -> Result<Option<&[u8]>, CborError> {
+        let length = self.fill_item()?;
+        Ok(length.map(move |length| &self.buffer[..length]))
+    }
+
+    pub fn next_value(&mut self) -> Result<Option<Value<'_>>, CborError> {
+        match self.fill_item()? {
+            Some(length) => {
+                let (value, _) = self.deserializer.take_value(&self.buffer[..length])?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn next_deserialize<'a, T: Deserialize<'a>>(&'a mut self) -> Result<Option<T>, CborError> {
+        match self.fill_item()? {
+            Some(length) => {
+                let data = &self.buffer[..length];
+                let (value, _) = self.deserializer.decode(data, &self.context)?;
+                Ok(Some(value))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn fill_item(&mut self) -> Result<Option<usize>, CborError> {
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+        self.deserializer.reset();
+        loop {
+            if !self.buffer.is_empty() {
+                match item_length(&self.deserializer, &self.buffer) {
+                    Ok(length) => {
+                        self.consumed = length;
+                        return Ok(Some(length));
+                    }
+                    Err(CborError::Incomplete(needed)) => {
+                        if self.read_chunk()? == 0 {
+                            return Err(CborError::Incomplete(needed));
+                        }
+                    }
+                    Err(e) => return Err(e),
+                }
+            } else if self.read_chunk()? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+
+    fn read_chunk(&mut self) -> Result<usize, CborError> {
+        let start = self.buffer.len();
+        self.buffer.resize(start + self.chunk_size, 0);
+        let result = loop {
+            match self.reader.read(&mut self.buffer[start..]) {
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        let read = *result.as_ref().unwrap_or(&0);
+        self.buffer.truncate(start + read);
+        result.map_err(CborError::from)
+    }
+}
+
+impl<R: Read> Iterator for StreamDeserializer<R> {
+    type Item = Result<Vec<u8>, CborError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.next_item() {
+            Ok(item) => item.map(|item| Ok(item.to_vec())),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn item_length(deserializer: &Deserializer, data: &[u8]) -> Result<usize, CborError> {
+    let remaining = deserializer.skip_value(data)?;
+    Ok(data.len() - remaining.len())
+}
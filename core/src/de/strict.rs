@@ -0,0 +1,127 @@
+use crate::de::Deserializer;
+use crate::error::CborError;
+use crate::types::{ByteSize, IanaTag, Integer, Length, Type};
+
+impl Deserializer {
+    /// Creates a deserializer which rejects input that is not valid according to RFC 8949 §5.
+    pub fn strict() -> Self {
+        let mut deserializer = Self::new();
+        deserializer.strict = true;
+        deserializer
+    }
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub(crate) fn check_shortest_head(
+        &self,
+        initial: u8,
+        cbor_type: Type,
+        data: &[u8],
+    ) -> Result<(), CborError> {
+        let size = match cbor_type {
+            Type::UnsignedInteger(Integer::Sized(size))
+            | Type::NegativeInteger(Integer::Sized(size))
+            | Type::Tag(Integer::Sized(size))
+            | Type::Bytes(Length::AdditionalBytes(size))
+            | Type::Text(Length::AdditionalBytes(size))
+            | Type::Array(Length::AdditionalBytes(size))
+            | Type::Map(Length::AdditionalBytes(size)) => size,
+            _ => return Ok(()),
+        };
+        let (_, value) = Integer::<u64>::Sized(size).take_value(data)?;
+        let min = match size {
+            ByteSize::Size1Byte => 24,
+            ByteSize::Size2Bytes => 0x100,
+            ByteSize::Size4Bytes => 0x1_0000,
+            ByteSize::Size8Bytes => 0x1_0000_0000,
+        };
+        if value < min {
+            Err(CborError::NotShortestForm(initial))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn check_tag_content(&self, tag: IanaTag, content: Type) -> Result<(), CborError> {
+        let valid = match tag {
+            IanaTag::DateTimeString
+            | IanaTag::Uri
+            | IanaTag::Base64Url
+            | IanaTag::Base64
+            | IanaTag::Regex
+            | IanaTag::MimeMessage => matches!(content, Type::Text(_)),
+            IanaTag::EpochBasedTime => match content {
+                Type::UnsignedInteger(_) | Type::NegativeInteger(_) => true,
+                Type::Special(special) => special.is_float(),
+                _ => false,
+            },
+            IanaTag::PositiveBigNum
+            | IanaTag::NegativeBigNum
+            | IanaTag::CborDataItem
+            | IanaTag::Uuid
+            | IanaTag::NetworkAddress
+            | IanaTag::Uint8Array
+            | IanaTag::Uint16BeArray
+            | IanaTag::Uint32BeArray
+            | IanaTag::Uint64BeArray
+            | IanaTag::Uint8ClampedArray
+            | IanaTag::Uint16LeArray
+            | IanaTag::Uint32LeArray
+            | IanaTag::Uint64LeArray
+            | IanaTag::Sint8Array
+            | IanaTag::Sint16BeArray
+            | IanaTag::Sint32BeArray
+            | IanaTag::Sint64BeArray
+            | IanaTag::Sint16LeArray
+            | IanaTag::Sint32LeArray
+            | IanaTag::Sint64LeArray
+            | IanaTag::F16BeArray
+            | IanaTag::F32BeArray
+            | IanaTag::F64BeArray
+            | IanaTag::F128BeArray
+            | IanaTag::F16LeArray
+            | IanaTag::F32LeArray
+            | IanaTag::F64LeArray
+            | IanaTag::F128LeArray => matches!(content, Type::Bytes(_)),
+            IanaTag::DecimalFraction | IanaTag::BigFloat | IanaTag::GeoCoordinate => {
+                matches!(content, Type::Array(_))
+            }
+            IanaTag::NetworkAddressPlusMask => matches!(content, Type::Array(_) | Type::Map(_)),
+            IanaTag::ExtendedTime => matches!(content, Type::Map(_)),
+            _ => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(CborError::InvalidTagContent(tag, content))
+        }
+    }
+
+    /// Fails in strict mode if the encoded map keys contain the same key twice.
+    pub(crate) fn check_unique_keys(&self, mut keys: Vec<&[u8]>) -> Result<(), CborError> {
+        if !self.strict {
+            return Ok(());
+        }
+        keys.sort_unstable();
+        match keys.windows(2).find(|pair| pair[0] == pair[1]) {
+            Some(pair) => Err(CborError::DuplicateMapKey(pair[0].to_vec())),
+            None => Ok(()),
+        }
+    }
+    pub fn check_duplicate_id(
+        &self,
+        found_ids: &[u64],
+        id: u64,
+        key: &[u8],
+    ) -> Result<(), CborError> {
+        if self.strict && found_ids.contains(&id) {
+            Err(CborError::DuplicateMapKey(key.to_vec()))
+        } else {
+            Ok(())
+        }
+    }
+}
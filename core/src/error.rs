@@ -1,5 +1,6 @@
 use crate::types::{IanaTag, Special, Type};
 
+use std::fmt;
 use std::str::Utf8Error;
 use thiserror::Error;
 
@@ -21,8 +22,8 @@ pub enum CborError {
     ExpectFloat(Special),
     #[error("expected break but got: {:?}", _0)]
     ExpectBreak(Special),
-    #[error("Got a nom parse error: {:?} [{:?}]", _1, _0)]
-    NomParseError(Vec<u8>, nom::error::ErrorKind),
+    #[error("Got a nom parse error: {:?}", _0)]
+    NomParseError(nom::error::ErrorKind),
     #[error("Incomplete byte array, needed: {:?}", _0)]
     Incomplete(nom::Needed),
     #[error("invalid utf8: {}", _0)]
@@ -41,6 +42,8 @@ pub enum CborError {
     ExpectNegative(Type),
     #[error("Expected special but got: {:?}", _0)]
     ExpectSpecial(Type),
+    #[error("Expected simple value but got: {:?}", _0)]
+    ExpectSimple(Type),
     #[error("Expected reduced special but got: {:?}", _0)]
     ExpectReducedSpecial(Special),
     #[error("Expected array but got: {:?}", _0)]
@@ -91,17 +94,119 @@ pub enum CborError {
     ExpectNumber(String),
     #[error("Infinite bytes and strings are not supported")]
     InfiniteNotSupported,
+    #[error("Expected definite chunk of the same major type but got: {:?}", _0)]
+    InvalidChunk(Type),
+    #[error("Expected a single item but {} bytes remained", _0)]
+    TrailingBytes(usize),
     #[error("No value found for {}", _0)]
     NoValueFound(&'static str),
+    #[error("Failed to read from or write to stream: {}", _0)]
+    IoError(String),
+    #[error("Output buffer of {} bytes is too small", _0)]
+    BufferOverflow(usize),
+    #[error("Indefinite lengths are not allowed in deterministic mode")]
+    IndefiniteNotAllowed,
+    #[error("Container is missing {} items", _0)]
+    MissingItems(u64),
+    #[error("Indefinite length item is missing its break")]
+    MissingBreak,
+    #[error("{} at offset {} ({})", error, offset, path)]
+    Located {
+        offset: usize,
+        path: DecodePath,
+        error: Box<CborError>,
+    },
+    #[error("Decode limit {} of {} exceeded", limit, max)]
+    LimitExceeded { limit: &'static str, max: usize },
+    #[error("Reserved additional information in initial byte: {:#04x}", _0)]
+    ReservedAdditionalInfo(u8),
+    #[error("Indefinite length is not allowed for initial byte: {:#04x}", _0)]
+    InvalidIndefiniteLength(u8),
+    #[error(
+        "Argument of initial byte {:#04x} is not encoded in its shortest form",
+        _0
+    )]
+    NotShortestForm(u8),
+    #[error("Simple value {} must be encoded in the initial byte", _0)]
+    InvalidSimpleValue(u8),
+    #[error("Invalid diagnostic notation at offset {}: {}", offset, reason)]
+    InvalidDiagnostic { offset: usize, reason: &'static str },
+    #[error("Value can not be converted to json: {}", _0)]
+    NotJsonCompatible(&'static str),
+    #[error("Break outside of an indefinite length item")]
+    UnexpectedBreak,
+    #[error("Integer {} does not fit into {}", value, target)]
+    IntegerOutOfRange { value: i128, target: &'static str },
+    #[error("Bignum does not fit into {}", _0)]
+    BignumOutOfRange(&'static str),
+    #[error("Expected a single character but got: {:?}", _0)]
+    ExpectChar(String),
+    #[error("Duplicate map key: {:02x?}", _0)]
+    DuplicateMapKey(Vec<u8>),
+    #[error("Tag {:?} can not be applied to: {:?}", _0, _1)]
+    InvalidTagContent(IanaTag, Type),
+    #[error("{}", _0)]
+    Custom(String),
+}
+
+impl CborError {
+    /// Byte offset of the item that failed to decode, counted from the start of the input
+    /// of `from_bytes`, `Deserializer::decode` or `Deserializer::take_value`.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            CborError::Located { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+    pub fn path(&self) -> Option<&DecodePath> {
+        match self {
+            CborError::Located { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+    /// Returns the error without its location.
+    pub fn inner(&self) -> &CborError {
+        match self {
+            CborError::Located { error, .. } => error,
+            e => e,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PathSegment {
+    Index(usize),
+    Key(String),
+    Field(u64, &'static str),
+    Tag(u64),
+}
+
+/// Location of a nested item, from the outermost item inwards.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct DecodePath(pub Vec<PathSegment>);
+
+impl fmt::Display for DecodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::Key(key) => write!(f, "[{}]", key)?,
+                PathSegment::Field(id, name) => write!(f, ".{}#{}", name, id)?,
+                PathSegment::Tag(tag) => write!(f, "<{}>", tag)?,
+            }
+        }
+        Ok(())
+    }
 }
 
 impl nom::error::ParseError<&[u8]> for CborError {
-    fn from_error_kind(input: &[u8], kind: nom::error::ErrorKind) -> Self {
-        CborError::NomParseError(input.to_vec(), kind)
+    fn from_error_kind(_input: &[u8], kind: nom::error::ErrorKind) -> Self {
+        CborError::NomParseError(kind)
     }
 
-    fn append(input: &[u8], kind: nom::error::ErrorKind, _other: Self) -> Self {
-        CborError::NomParseError(input.to_vec(), kind)
+    fn append(_input: &[u8], kind: nom::error::ErrorKind, _other: Self) -> Self {
+        CborError::NomParseError(kind)
     }
 }
 
@@ -115,6 +220,26 @@ impl From<nom::Err<CborError>> for CborError {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::ser::Error for CborError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        CborError::Custom(msg.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for CborError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        CborError::Custom(msg.to_string())
+    }
+}
+
+impl From<std::io::Error> for CborError {
+    fn from(e: std::io::Error) -> Self {
+        CborError::IoError(e.to_string())
+    }
+}
+
 impl From<Utf8Error> for CborError {
     fn from(e: Utf8Error) -> Self {
         CborError::InvalidUtf8(e)
@@ -1,16 +1,28 @@
+use std::io::Write;
+
 use bytes::BytesMut;
 
-pub use context::Context;
-pub use de::{Deserialize, Deserializer};
-pub use error::CborError;
-pub use ser::{Serialize, Serializer};
+pub use context::{Context, DateTimeFormat, Endianness, OptionEncoding, Precision};
+#[cfg(feature = "serde")]
+pub use de::SerdeDeserializer;
+pub use de::{ArrayIter, DecodeLimits, Deserialize, Deserializer, MapIter, StreamDeserializer};
+pub use error::{CborError, DecodePath, PathSegment};
+pub use parser::{Event, Parser};
+#[cfg(feature = "serde")]
+pub use ser::SerdeCompound;
+pub use ser::{
+    canonicalize, BytesChunks, FloatEncoding, LengthCheck, Serialize, Serializer, TextChunks,
+};
 pub use types::*;
-pub use value::Value;
+#[cfg(feature = "json")]
+pub use value::JsonPolicy;
+pub use value::{OwnedValue, RawValue, Value};
 
 mod context;
 mod convert_slice;
 mod de;
 mod error;
+mod parser;
 mod ser;
 mod types;
 mod value;
@@ -30,7 +42,41 @@ pub fn to_vec<T: Serialize>(t: &T) -> Vec<u8> {
     serializer.into_bytes().to_vec()
 }
 
+/// Serializes straight into an `io::Write`, wrap unbuffered writers in a `BufWriter`.
+pub fn to_writer<T: Serialize, W: Write>(t: &T, writer: W) -> Result<usize, CborError> {
+    let mut serializer = Serializer::from_writer(writer);
+    t.serialize(&mut serializer, &Context::new());
+    serializer.finish()
+}
+
+/// Serializes into `slice` and returns the number of bytes written.
+pub fn to_slice<T: Serialize>(t: &T, slice: &mut [u8]) -> Result<usize, CborError> {
+    let mut serializer = Serializer::from_slice(slice);
+    t.serialize(&mut serializer, &Context::new());
+    serializer.finish()
+}
+
 pub fn from_bytes<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, CborError> {
     let mut deserializer = Deserializer::new();
-    T::deserialize(&mut deserializer, bytes, &Context::new()).map(|t| t.0)
+    deserializer.decode(bytes, &Context::new()).map(|t| t.0)
+}
+
+/// Serializes any `serde::Serialize` type, see `ser::Serializer for &mut Serializer` for the mapping.
+#[cfg(feature = "serde")]
+pub fn to_vec_serde<T: serde::Serialize + ?Sized>(t: &T) -> Result<Vec<u8>, CborError> {
+    let mut serializer = Serializer::new();
+    t.serialize(&mut serializer)?;
+    if let Some(e) = serializer.error() {
+        return Err(e.clone());
+    }
+    Ok(serializer.into_bytes().to_vec())
+}
+
+/// Deserializes any `serde::Deserialize` type, borrowing strings and bytes from `bytes`.
+#[cfg(feature = "serde")]
+pub fn from_bytes_serde<'de, T: serde::Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, CborError> {
+    let mut deserializer = Deserializer::new();
+    let top_level = deserializer.begin_top_level(bytes);
+    let result = T::deserialize(&mut SerdeDeserializer::new(&mut deserializer, bytes));
+    deserializer.end_top_level(top_level, result)
 }
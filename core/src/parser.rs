@@ -0,0 +1,225 @@
+use std::str::from_utf8;
+
+use nom::bytes::streaming::take;
+
+use crate::de::{DecodeLimits, Deserializer, Remaining};
+use crate::error::CborError;
+use crate::types::{IanaTag, Length, Special, Type};
+
+/// A single step of a cbor document as reported by the `Parser`.
+///
+/// Definite strings are reported as one `Bytes` / `Text` event, indefinite strings as
+/// `StartBytes` / `StartText` followed by their chunks and a `Break`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<'de> {
+    Unsigned(u64),
+    Negative(i128),
+    Bytes(&'de [u8]),
+    Text(&'de str),
+    StartBytes,
+    StartText,
+    StartArray(Option<usize>),
+    StartMap(Option<usize>),
+    Tag(IanaTag),
+    Bool(bool),
+    Null,
+    Undefined,
+    Simple(u8),
+    Float(f64),
+    Break,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Frame {
+    Items(usize),
+    Indefinite,
+    Chunks(fn(Type) -> bool),
+}
+
+/// Pull parser reporting the items of a cbor sequence as events without allocating them.
+pub struct Parser<'de> {
+    deserializer: Deserializer,
+    data: &'de [u8],
+    remaining: Remaining<'de>,
+    stack: Vec<Frame>,
+    /// Set after a tag until its content starts.
+    pending_tag: bool,
+    failed: bool,
+}
+
+impl<'de> Parser<'de> {
+    pub fn new(data: &'de [u8]) -> Self {
+        Self {
+            deserializer: Deserializer::new(),
+            data,
+            remaining: data,
+            stack: Vec::new(),
+            pending_tag: false,
+            failed: false,
+        }
+    }
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.deserializer.set_limits(limits);
+        self
+    }
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.deserializer.set_strict(strict);
+        self
+    }
+
+    /// Number of arrays, maps and indefinite strings the next event is nested in.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+    /// Byte offset of the next event.
+    pub fn offset(&self) -> usize {
+        self.data.len() - self.remaining.len()
+    }
+    pub fn remaining(&self) -> Remaining<'de> {
+        self.remaining
+    }
+
+    pub fn next_event(&mut self) -> Result<Option<Event<'de>>, CborError> {
+        if self.remaining.is_empty() {
+            return if self.stack.is_empty() && !self.pending_tag {
+                Ok(None)
+            } else {
+                Err(CborError::Incomplete(nom::Needed::Unknown))
+            };
+        }
+        let data = self.remaining;
+        let (cbor_type, remaining) = self.deserializer.take_type(data, false)?;
+        if let Some(Frame::Chunks(is_chunk)) = self.stack.last() {
+            if !is_chunk(cbor_type) && cbor_type != Type::Special(Special::Break) {
+                return Err(CborError::InvalidChunk(cbor_type));
+            }
+        }
+        if self.pending_tag && cbor_type == Type::Special(Special::Break) {
+            return Err(CborError::UnexpectedBreak);
+        }
+        self.pending_tag = matches!(cbor_type, Type::Tag(_));
+        let (event, remaining) = match cbor_type {
+            Type::UnsignedInteger(int) => {
+                let (remaining, value) = int.take_value(remaining)?;
+                (Event::Unsigned(value), remaining)
+            }
+            Type::NegativeInteger(int) => {
+                let (remaining, value) = int.take_value(remaining)?;
+                (Event::Negative(value), remaining)
+            }
+            Type::Bytes(length) | Type::Text(length) => {
+                let (remaining, length) = length.take_length_to_read(remaining)?;
+                match length {
+                    Some(length) => {
+                        self.deserializer.check_string_length(length)?;
+                        let (remaining, bytes) = take(length)(remaining)?;
+                        match cbor_type {
+                            Type::Bytes(_) => (Event::Bytes(bytes), remaining),
+                            _ => (Event::Text(from_utf8(bytes)?), remaining),
+                        }
+                    }
+                    None => {
+                        let (is_chunk, event): (fn(Type) -> bool, _) = match cbor_type {
+                            Type::Bytes(_) => (is_bytes_chunk, Event::StartBytes),
+                            _ => (is_text_chunk, Event::StartText),
+                        };
+                        self.push(data, Frame::Chunks(is_chunk))?;
+                        self.remaining = remaining;
+                        return Ok(Some(event));
+                    }
+                }
+            }
+            Type::Array(length) | Type::Map(length) => {
+                let (remaining, length) = length.take_length_to_read(remaining)?;
+                let (event, factor) = match cbor_type {
+                    Type::Array(_) => (Event::StartArray(length), 1),
+                    _ => (Event::StartMap(length), 2),
+                };
+                let frame = match length {
+                    Some(0) => None,
+                    Some(length) => {
+                        self.deserializer.check_collection_length(length)?;
+                        Some(Frame::Items(length.saturating_mul(factor)))
+                    }
+                    None => Some(Frame::Indefinite),
+                };
+                match frame {
+                    Some(frame) => {
+                        self.push(data, frame)?;
+                        self.remaining = remaining;
+                        return Ok(Some(event));
+                    }
+                    None => (event, remaining),
+                }
+            }
+            Type::Tag(int) => {
+                let (remaining, tag) = int.take_value(remaining)?;
+                self.remaining = remaining;
+                return Ok(Some(Event::Tag(IanaTag::from_tag(tag))));
+            }
+            Type::Special(special) => match special {
+                Special::Bool(val) => (Event::Bool(val), remaining),
+                Special::Null => (Event::Null, remaining),
+                Special::Undefined => (Event::Undefined, remaining),
+                Special::Simple(val) => (Event::Simple(val), remaining),
+                Special::Break => match self.stack.last() {
+                    Some(Frame::Indefinite) | Some(Frame::Chunks(_)) => {
+                        self.pop();
+                        (Event::Break, remaining)
+                    }
+                    _ => return Err(CborError::UnexpectedBreak),
+                },
+                _ => {
+                    let (value, remaining) = self.deserializer.take_float(data, false)?;
+                    (Event::Float(value), remaining)
+                }
+            },
+        };
+        self.remaining = remaining;
+        self.complete_item();
+        Ok(Some(event))
+    }
+
+    fn push(&mut self, data: &[u8], frame: Frame) -> Result<(), CborError> {
+        self.deserializer.enter_nested(data)?;
+        self.stack.push(frame);
+        Ok(())
+    }
+    fn pop(&mut self) {
+        self.deserializer.leave_nested();
+        self.stack.pop();
+    }
+    fn complete_item(&mut self) {
+        while let Some(Frame::Items(items)) = self.stack.last_mut() {
+            *items -= 1;
+            if *items > 0 {
+                break;
+            }
+            self.pop();
+        }
+    }
+}
+
+fn is_bytes_chunk(cbor_type: Type) -> bool {
+    matches!(cbor_type, Type::Bytes(length) if length != Length::Indefinite)
+}
+fn is_text_chunk(cbor_type: Type) -> bool {
+    matches!(cbor_type, Type::Text(length) if length != Length::Indefinite)
+}
+
+impl<'de> Iterator for Parser<'de> {
+    type Item = Result<Event<'de>, CborError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.next_event() {
+            Ok(event) => event.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use bytes::BytesMut;
+
+use crate::error::CborError;
+use crate::ser::output::{Output, Sink};
+use crate::ser::Serializer;
+
+/// What happens when the written items do not match the length of their container.
+///
+/// Checking costs some bookkeeping per item and is meant for debug builds, e.g. only set it
+/// when `cfg!(debug_assertions)` holds.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LengthCheck {
+    Off,
+    /// Records the mismatch, it is returned by `error` and `finish`.
+    Error,
+    Panic,
+}
+
+/// An open container while items are tracked.
+pub(crate) enum Frame {
+    Items(u64),
+    Indefinite,
+    Chunks,
+    /// Start offsets of the items written inside `array` or `map`.
+    Count(Vec<usize>),
+}
+
+pub(crate) struct Tracker {
+    pub(crate) check: LengthCheck,
+    frames: Vec<Frame>,
+    pending_tag: bool,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self {
+            check: LengthCheck::Off,
+            frames: Vec::new(),
+            pending_tag: false,
+        }
+    }
+}
+
+/// The state `begin_items` replaced, restored by `end_array` or `end_map`.
+pub(crate) struct Items<'a> {
+    output: Output<'a>,
+    pending_tag: bool,
+    level: usize,
+}
+
+impl Tracker {
+    pub(crate) fn reset(&mut self) {
+        self.frames.clear();
+        self.pending_tag = false;
+    }
+}
+
+impl<'a> Serializer<'a> {
+    pub fn set_length_check(&mut self, check: LengthCheck) {
+        self.tracker.check = check;
+    }
+    pub fn length_check(&self) -> LengthCheck {
+        self.tracker.check
+    }
+
+    /// Writes an array whose length is the number of items `build` writes.
+    ///
+    /// The items are buffered and the head is written afterwards in its shortest form.
+    pub fn array<F: FnOnce(&mut Self)>(&mut self, build: F) {
+        let items = self.begin_items();
+        build(self);
+        self.end_array(items);
+    }
+    /// Writes a map of the keys and values `build` writes alternately.
+    ///
+    /// In deterministic mode the entries are sorted by their encoded keys.
+    pub fn map<F: FnOnce(&mut Self)>(&mut self, build: F) {
+        let items = self.begin_items();
+        build(self);
+        self.end_map(items);
+    }
+
+    /// Redirects the output into a buffer until `end_array` or `end_map` is called.
+    pub(crate) fn begin_items(&mut self) -> Items<'a> {
+        let output = std::mem::replace(&mut self.output, Output::new(Sink::Bytes(BytesMut::new())));
+        let pending_tag = std::mem::replace(&mut self.tracker.pending_tag, false);
+        let level = self.tracker.frames.len();
+        self.tracker.frames.push(Frame::Count(Vec::new()));
+        Items {
+            output,
+            pending_tag,
+            level,
+        }
+    }
+    pub(crate) fn end_array(&mut self, items: Items<'a>) {
+        let (written, starts) = self.end_items(items);
+        self.track_raw_item();
+        self.put_head(starts.len() as u64, 0b1000_0000);
+        self.output.put_slice(&written);
+    }
+    pub(crate) fn end_map(&mut self, items: Items<'a>) {
+        let (written, starts) = self.end_items(items);
+        if starts.len() % 2 == 1 {
+            self.length_mismatch(CborError::MissingItems(1));
+        }
+        let mut entries: Vec<(usize, usize, usize)> = starts
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(index, entry)| {
+                let end = starts.get(index * 2 + 2).copied().unwrap_or(written.len());
+                (entry[0], entry[1], end)
+            })
+            .collect();
+        if self.deterministic {
+            entries.sort_by(|a, b| written[a.0..a.1].cmp(&written[b.0..b.1]));
+            self.check_sorted_keys(&written, &entries);
+        }
+        self.track_raw_item();
+        self.put_head(entries.len() as u64, 0b1010_0000);
+        for (start, _, end) in entries {
+            self.output.put_slice(&written[start..end]);
+        }
+    }
+
+    /// Drops the buffered items and puts the outer output back.
+    #[cfg(feature = "serde")]
+    pub(crate) fn abort_items(&mut self, items: Items<'a>) {
+        self.tracker.frames.truncate(items.level);
+        self.tracker.pending_tag = items.pending_tag;
+        let mut written = std::mem::replace(&mut self.output, items.output);
+        if let Some(error) = written.take_error() {
+            self.output.fail(error);
+        }
+    }
+
+    fn end_items(&mut self, items: Items<'a>) -> (BytesMut, Vec<usize>) {
+        let level = items.level;
+        if let Some(missing) = self.tracker.frames.get(level + 1) {
+            let error = match missing {
+                Frame::Items(missing) => CborError::MissingItems(*missing),
+                _ => CborError::MissingBreak,
+            };
+            self.length_mismatch(error);
+        }
+        let starts = match self.tracker.frames.drain(level..).next() {
+            Some(Frame::Count(starts)) => starts,
+            _ => Vec::new(),
+        };
+        self.tracker.pending_tag = items.pending_tag;
+        let mut written = std::mem::replace(&mut self.output, items.output);
+        if let Some(error) = written.take_error() {
+            self.output.fail(error);
+        }
+        (written.into_bytes(), starts)
+    }
+
+    fn tracking(&self) -> bool {
+        self.tracker.check != LengthCheck::Off || !self.tracker.frames.is_empty()
+    }
+
+    /// Accounts a head about to be written, `argument` is `None` for indefinite lengths and breaks.
+    pub(crate) fn track_head(&mut self, mask: u8, argument: Option<u64>) {
+        if !self.tracking() {
+            return;
+        }
+        let major = mask >> 5;
+        if major == 7 && argument.is_none() {
+            return match self.tracker.frames.last() {
+                Some(Frame::Indefinite) | Some(Frame::Chunks) => {
+                    self.tracker.frames.pop();
+                    self.complete_item();
+                }
+                _ => self.length_mismatch(CborError::UnexpectedBreak),
+            };
+        }
+        self.start_item();
+        self.tracker.pending_tag = major == 6;
+        match (major, argument) {
+            (6, _) => {}
+            (2, None) | (3, None) => self.tracker.frames.push(Frame::Chunks),
+            (4, None) | (5, None) => self.tracker.frames.push(Frame::Indefinite),
+            (4, Some(length)) | (5, Some(length)) if length > 0 => {
+                let items = if major == 5 { length * 2 } else { length };
+                self.tracker.frames.push(Frame::Items(items));
+            }
+            _ => self.complete_item(),
+        }
+    }
+    /// Accounts one complete item written without heads passing `track_head`.
+    pub(crate) fn track_raw_item(&mut self) {
+        if self.tracking() {
+            self.start_item();
+            self.tracker.pending_tag = false;
+            self.complete_item();
+        }
+    }
+
+    fn start_item(&mut self) {
+        if self.tracker.pending_tag {
+            return;
+        }
+        let position = self.output.len();
+        if let Some(Frame::Count(starts)) = self.tracker.frames.last_mut() {
+            starts.push(position);
+        }
+    }
+    fn complete_item(&mut self) {
+        while let Some(Frame::Items(remaining)) = self.tracker.frames.last_mut() {
+            *remaining -= 1;
+            if *remaining > 0 {
+                return;
+            }
+            self.tracker.frames.pop();
+        }
+    }
+
+    fn length_mismatch(&mut self, error: CborError) {
+        match self.tracker.check {
+            LengthCheck::Off => {}
+            LengthCheck::Error => self.output.fail(error),
+            LengthCheck::Panic => panic!("{}", error),
+        }
+    }
+
+    /// Reports containers which are still missing items or a break.
+    pub(crate) fn check_complete(&mut self) {
+        let error = match self.tracker.frames.last() {
+            Some(Frame::Items(missing)) => CborError::MissingItems(*missing),
+            Some(_) => CborError::MissingBreak,
+            None => return,
+        };
+        self.length_mismatch(error);
+    }
+}
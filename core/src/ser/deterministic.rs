@@ -0,0 +1,111 @@
+use bytes::BytesMut;
+
+use crate::context::Context;
+use crate::de::Deserializer;
+use crate::error::CborError;
+use crate::ser::output::{Output, Sink};
+use crate::ser::Serializer;
+
+impl Serializer<'static> {
+    /// Creates a serializer producing the deterministic encoding of RFC 8949 §4.2.1.
+    ///
+    /// Map entries are sorted by their encoded keys and floats use the shortest lossless width.
+    pub fn deterministic() -> Self {
+        let mut serializer = Self::new();
+        serializer.deterministic = true;
+        serializer
+    }
+}
+
+impl Serializer<'_> {
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Like `write_map_entries`, also sorting the entries if `Context::canonical` is set.
+    pub(crate) fn write_map_entries_with_context<I, K, V>(
+        &mut self,
+        context: &Context,
+        entries: I,
+        write_key: K,
+        write_value: V,
+    ) where
+        I: ExactSizeIterator,
+        K: FnMut(&mut Self, &I::Item),
+        V: FnMut(&mut Self, &I::Item),
+    {
+        let deterministic = self.deterministic;
+        self.deterministic |= context.canonical;
+        self.write_map_entries(entries, write_key, write_value);
+        self.deterministic = deterministic;
+    }
+
+    /// Writes a map header and its entries, sorted by their encoded keys in deterministic mode.
+    pub fn write_map_entries<I, K, V>(&mut self, entries: I, mut write_key: K, mut write_value: V)
+    where
+        I: ExactSizeIterator,
+        K: FnMut(&mut Self, &I::Item),
+        V: FnMut(&mut Self, &I::Item),
+    {
+        self.write_map_def(entries.len());
+        if !self.deterministic {
+            for entry in entries {
+                write_key(self, &entry);
+                write_value(self, &entry);
+            }
+            return;
+        }
+        // entries are collected in a buffer, the sink might not allow rewriting them
+        let output = std::mem::replace(&mut self.output, Output::new(Sink::Bytes(BytesMut::new())));
+        let mut ranges = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let key_start = self.output.len();
+            write_key(self, &entry);
+            let key_end = self.output.len();
+            write_value(self, &entry);
+            ranges.push((key_start, key_end, self.output.len()));
+        }
+        let mut written = std::mem::replace(&mut self.output, output);
+        if let Some(error) = written.take_error() {
+            self.output.fail(error);
+        }
+        let written = written.into_bytes();
+        ranges.sort_by(|a, b| written[a.0..a.1].cmp(&written[b.0..b.1]));
+        self.check_sorted_keys(&written, &ranges);
+        for (entry_start, _, entry_end) in ranges {
+            self.output.put_slice(&written[entry_start..entry_end]);
+        }
+    }
+
+    /// Fails the serializer if two of the sorted entries have the same encoded key.
+    pub(crate) fn check_sorted_keys(&mut self, written: &[u8], entries: &[(usize, usize, usize)]) {
+        let duplicate = entries
+            .windows(2)
+            .find(|pair| written[pair[0].0..pair[0].1] == written[pair[1].0..pair[1].1]);
+        if let Some(pair) = duplicate {
+            self.output.fail(CborError::DuplicateMapKey(
+                written[pair[0].0..pair[0].1].to_vec(),
+            ));
+        }
+    }
+}
+
+/// Re-encodes a single cbor item in the deterministic encoding of RFC 8949 §4.2.1.
+///
+/// Indefinite lengths become definite ones, heads, floats and map key order are normalized.
+/// Duplicate map keys and breaks outside of indefinite items are rejected.
+pub fn canonicalize(data: &[u8]) -> Result<Vec<u8>, CborError> {
+    let (value, remaining) = Deserializer::new().take_value(data)?;
+    if !remaining.is_empty() {
+        return Err(CborError::TrailingBytes(remaining.len()));
+    }
+    let mut serializer = Serializer::deterministic();
+    serializer.write_value(&value);
+    if let Some(error) = serializer.error() {
+        return Err(error.clone());
+    }
+    Ok(serializer.into_bytes().to_vec())
+}
@@ -6,7 +6,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_biguint(&mut self, uint: &BigUint) {
         self.write_tag(IanaTag::PositiveBigNum);
         self.write_bytes(uint.to_bytes_be().as_slice());
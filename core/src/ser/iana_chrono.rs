@@ -1,20 +1,12 @@
 use chrono::offset::TimeZone;
 use chrono::{Date, DateTime, FixedOffset, NaiveDate, NaiveDateTime, Offset, Timelike, Utc};
 
-use crate::context::Context;
+use crate::context::{Context, DateTimeFormat, Precision};
 use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-pub enum Precision {
-    Float,
-    Seconds,
-    Millis,
-    Micros,
-    Nanos,
-}
-
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_datetime_as_string(&mut self, timestamp: &DateTime<FixedOffset>) {
         self.write_tag(IanaTag::DateTimeString);
         let string = timestamp.to_rfc3339();
@@ -85,7 +77,10 @@ impl Serializer {
 }
 
 impl Serialize for DateTime<FixedOffset> {
-    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
-        serializer.write_datetime(self, Precision::Nanos);
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        match context.datetime_format {
+            DateTimeFormat::Epoch => serializer.write_datetime(self, context.datetime_precision),
+            DateTimeFormat::Rfc3339 => serializer.write_datetime_as_string(self),
+        }
     }
 }
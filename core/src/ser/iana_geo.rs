@@ -4,7 +4,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_geo(&mut self, coordinate: &GeoCoordinate) {
         self.write_tag(IanaTag::GeoCoordinate);
 
@@ -5,7 +5,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_mime_as_string(&mut self, mime: &Mime) {
         self.write_tag(IanaTag::MimeMessage);
         self.write_text(mime.as_ref());
@@ -5,7 +5,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_regex_as_string(&mut self, regex: &Regex) {
         self.write_tag(IanaTag::Regex);
         self.write_text(regex.as_str());
@@ -5,7 +5,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_ip_address(&mut self, address: &IpAddr) {
         self.write_tag(IanaTag::NetworkAddress);
         match address {
@@ -5,7 +5,7 @@ use crate::ser::Serializer;
 use crate::types::IanaTag;
 use crate::Serialize;
 
-impl Serializer {
+impl Serializer<'_> {
     pub fn write_uuid(&mut self, uuid: &Uuid) {
         self.write_tag(IanaTag::Uuid);
         self.write_bytes(uuid.as_bytes());
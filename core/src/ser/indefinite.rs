@@ -0,0 +1,142 @@
+use std::io::{ErrorKind, Read};
+
+use crate::error::CborError;
+use crate::ser::Serializer;
+
+impl<'a> Serializer<'a> {
+    /// Starts an array of unknown length, finish it with `write_break`.
+    ///
+    /// Deterministic encoding forbids indefinite lengths, the serializer fails with
+    /// `IndefiniteNotAllowed` in that mode.
+    pub fn begin_array_indefinite(&mut self) {
+        self.write_indefinite_head(0b1000_0000);
+    }
+    /// Starts a map of unknown length, finish it with `write_break`.
+    pub fn begin_map_indefinite(&mut self) {
+        self.write_indefinite_head(0b1010_0000);
+    }
+    /// Starts a byte string written in chunks, it is finished when the returned writer is dropped.
+    ///
+    /// In deterministic mode the chunks are collected and written as one definite byte string.
+    pub fn begin_bytes_chunked<'s>(&'s mut self) -> BytesChunks<'s, 'a> {
+        BytesChunks {
+            chunks: Chunks::begin(self, 0b0100_0000),
+        }
+    }
+    /// Starts a text string written in chunks, it is finished when the returned writer is dropped.
+    ///
+    /// In deterministic mode the chunks are collected and written as one definite text string.
+    pub fn begin_text_chunked<'s>(&'s mut self) -> TextChunks<'s, 'a> {
+        TextChunks {
+            chunks: Chunks::begin(self, 0b0110_0000),
+        }
+    }
+
+    /// Copies everything from `reader` into a chunked byte string with chunks of `chunk_size` bytes.
+    ///
+    /// Returns the number of copied bytes, after a read error the byte string ends with the bytes copied so far.
+    pub fn write_bytes_from_reader<R: Read>(
+        &mut self,
+        mut reader: R,
+        chunk_size: usize,
+    ) -> Result<u64, CborError> {
+        let mut buffer = vec![0u8; chunk_size.max(1)];
+        let mut chunks = self.begin_bytes_chunked();
+        let mut copied = 0u64;
+        loop {
+            let mut filled = 0;
+            while filled < buffer.len() {
+                match reader.read(&mut buffer[filled..]) {
+                    Ok(0) => break,
+                    Ok(read) => filled += read,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            if filled == 0 {
+                return Ok(copied);
+            }
+            chunks.write_chunk(&buffer[..filled]);
+            copied += filled as u64;
+        }
+    }
+
+    fn write_indefinite_head(&mut self, mask: u8) {
+        if self.deterministic {
+            self.output.fail(CborError::IndefiniteNotAllowed);
+        } else {
+            self.track_head(mask, None);
+            self.output.put_u8(mask | 31);
+        }
+    }
+}
+
+struct Chunks<'s, 'a> {
+    serializer: &'s mut Serializer<'a>,
+    mask: u8,
+    buffer: Option<Vec<u8>>,
+}
+
+impl<'s, 'a> Chunks<'s, 'a> {
+    fn begin(serializer: &'s mut Serializer<'a>, mask: u8) -> Self {
+        let buffer = if serializer.deterministic {
+            Some(Vec::new())
+        } else {
+            serializer.track_head(mask, None);
+            serializer.output.put_u8(mask | 31);
+            None
+        };
+        Self {
+            serializer,
+            mask,
+            buffer,
+        }
+    }
+    fn write_chunk(&mut self, chunk: &[u8]) {
+        match &mut self.buffer {
+            Some(buffer) => buffer.extend_from_slice(chunk),
+            None => {
+                self.serializer
+                    .write_u64_internal(chunk.len() as u64, self.mask);
+                self.serializer.output.put_slice(chunk);
+            }
+        }
+    }
+}
+
+impl Drop for Chunks<'_, '_> {
+    fn drop(&mut self) {
+        match self.buffer.take() {
+            Some(buffer) => {
+                self.serializer
+                    .write_u64_internal(buffer.len() as u64, self.mask);
+                self.serializer.output.put_slice(&buffer);
+            }
+            None => self.serializer.write_break(),
+        }
+    }
+}
+
+/// Writes the chunks of an indefinite length byte string, the break is written on drop.
+pub struct BytesChunks<'s, 'a> {
+    chunks: Chunks<'s, 'a>,
+}
+
+impl BytesChunks<'_, '_> {
+    pub fn write_chunk(&mut self, chunk: &[u8]) {
+        self.chunks.write_chunk(chunk);
+    }
+    pub fn finish(self) {}
+}
+
+/// Writes the chunks of an indefinite length text string, the break is written on drop.
+pub struct TextChunks<'s, 'a> {
+    chunks: Chunks<'s, 'a>,
+}
+
+impl TextChunks<'_, '_> {
+    pub fn write_chunk(&mut self, chunk: &str) {
+        self.chunks.write_chunk(chunk.as_bytes());
+    }
+    pub fn finish(self) {}
+}
@@ -1,16 +1,31 @@
-use std::collections::{BTreeMap, HashMap};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
-use bytes::{BufMut, BytesMut};
+use bytes::BytesMut;
 #[cfg(feature = "iana_numbers")]
 use half::f16;
 
-use crate::context::Context;
+#[cfg(feature = "iana_std")]
+use crate::context::Endianness;
+use crate::context::{Context, OptionEncoding};
+use crate::error::CborError;
+use crate::types::float::f64_to_f16;
 use crate::types::{IanaTag, MAX_INLINE_ENCODING};
 use crate::{ReducedSpecial, Value};
-use nom::AsBytes;
 use std::rc::Rc;
 use std::sync::Arc;
 
+pub use builder::LengthCheck;
+pub use deterministic::canonicalize;
+pub use indefinite::{BytesChunks, TextChunks};
+#[cfg(feature = "serde")]
+pub use serde_impl::SerdeCompound;
+
+use builder::Tracker;
+use output::{Output, Sink};
+
+mod builder;
+mod deterministic;
 #[cfg(feature = "iana_bigint")]
 mod iana_bigint;
 #[cfg(feature = "iana_chrono")]
@@ -27,40 +42,71 @@ mod iana_regex;
 mod iana_std;
 #[cfg(feature = "iana_uuid")]
 mod iana_uuid;
+mod indefinite;
+mod output;
+#[cfg(feature = "serde")]
+mod serde_impl;
 
 pub trait Serialize
 where
     Self: Sized,
 {
     fn serialize(&self, serializer: &mut Serializer, context: &Context);
+
+    /// Writes a slice of `Self`, `u8` overrides it to write a byte string.
+    fn serialize_slice(slice: &[Self], serializer: &mut Serializer, context: &Context) {
+        serializer.write_array_def(slice.len());
+        slice.iter().for_each(|e| e.serialize(serializer, context));
+    }
+}
+
+/// Selects how `Serialize` for `f32` and `f64` encodes floats, see `Context::float_encoding`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum FloatEncoding {
+    /// Always the declared width.
+    Declared,
+    /// The shortest width representing the value without loss, see `write_float_preferred`.
+    Preferred,
 }
 
-pub struct Serializer {
-    bytes: BytesMut,
+/// Writes cbor into a `BytesMut` by default, see `from_writer`, `from_buf_mut` and `from_slice` for other sinks.
+pub struct Serializer<'a> {
+    output: Output<'a>,
+    deterministic: bool,
+    tracker: Tracker,
 }
 
-impl AsRef<[u8]> for Serializer {
+impl AsRef<[u8]> for Serializer<'_> {
     fn as_ref(&self) -> &[u8] {
-        self.bytes.as_ref()
+        self.output.as_bytes()
     }
 }
-impl Default for Serializer {
+impl Default for Serializer<'static> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Serializer {
+impl Serializer<'static> {
     pub fn new() -> Self {
-        Serializer {
-            bytes: BytesMut::new(),
-        }
+        Self::with_bytes(BytesMut::new())
     }
     pub fn with_bytes(bytes: BytesMut) -> Self {
-        Self { bytes }
+        Self::with_output(Output::new(Sink::Bytes(bytes)))
+    }
+}
+
+impl<'a> Serializer<'a> {
+    fn with_output(output: Output<'a>) -> Self {
+        Self {
+            output,
+            deterministic: false,
+            tracker: Tracker::default(),
+        }
     }
     pub fn reset(&mut self) {
-        self.bytes.clear();
+        self.output.clear();
+        self.tracker.reset();
     }
     pub fn write_array_def(&mut self, length: usize) {
         self.write_u64_internal(length as u64, 0b1000_0000);
@@ -70,22 +116,32 @@ impl Serializer {
     }
     pub fn write_bytes(&mut self, bytes: &[u8]) {
         self.write_u64_internal(bytes.len() as u64, 0b0100_0000);
-        self.bytes.reserve(bytes.len());
-        self.bytes.put_slice(bytes);
+        self.output.reserve(bytes.len());
+        self.output.put_slice(bytes);
+    }
+    /// Writes already encoded cbor verbatim.
+    pub fn write_raw(&mut self, bytes: &[u8]) {
+        self.track_raw_item();
+        self.output.reserve(bytes.len());
+        self.output.put_slice(bytes);
     }
     pub fn write_string(&mut self, text: &str) {
         self.write_text(text);
     }
     pub fn write_text(&mut self, text: &str) {
         self.write_u64_internal(text.len() as u64, 0b0110_0000);
-        self.bytes.reserve(text.len());
-        self.bytes.put_slice(text.as_bytes());
+        self.output.reserve(text.len());
+        self.output.put_slice(text.as_bytes());
     }
     pub fn write_u64(&mut self, value: u64) {
         self.write_u64_internal(value, 0u8);
     }
 
     fn write_u64_internal(&mut self, value: u64, mask: u8) {
+        self.track_head(mask, Some(value));
+        self.put_head(value, mask);
+    }
+    fn put_head(&mut self, value: u64, mask: u8) {
         let slice: [u8; 8] = value.to_be_bytes();
         let option = slice
             .iter()
@@ -93,72 +149,110 @@ impl Serializer {
             .find(|(_, b)| **b > 0u8)
             .map(|(pos, _)| pos);
         if value <= (MAX_INLINE_ENCODING as u64) {
-            self.bytes.reserve(1);
-            self.bytes.put_u8(mask | value as u8)
+            self.output.reserve(1);
+            self.output.put_u8(mask | value as u8)
         } else if let Some(len) = option {
             if len == 7 {
-                self.bytes.reserve(2);
-                self.bytes.put_u8(mask | 24);
-                self.bytes.put_u8(slice[7]);
+                self.output.reserve(2);
+                self.output.put_u8(mask | 24);
+                self.output.put_u8(slice[7]);
             } else if len >= 6 {
-                self.bytes.reserve(3);
-                self.bytes.put_u8(mask | 25);
-                self.bytes.put_u8(slice[6]);
-                self.bytes.put_u8(slice[7]);
+                self.output.reserve(3);
+                self.output.put_u8(mask | 25);
+                self.output.put_u8(slice[6]);
+                self.output.put_u8(slice[7]);
             } else if len >= 4 {
-                self.bytes.reserve(5);
-                self.bytes.put_u8(mask | 26);
-                self.bytes.put_u8(slice[4]);
-                self.bytes.put_u8(slice[5]);
-                self.bytes.put_u8(slice[6]);
-                self.bytes.put_u8(slice[7]);
+                self.output.reserve(5);
+                self.output.put_u8(mask | 26);
+                self.output.put_u8(slice[4]);
+                self.output.put_u8(slice[5]);
+                self.output.put_u8(slice[6]);
+                self.output.put_u8(slice[7]);
             } else {
-                self.bytes.reserve(9);
-                self.bytes.put_u8(mask | 27);
-                self.bytes.put_u64(value);
+                self.output.reserve(9);
+                self.output.put_u8(mask | 27);
+                self.output.put_u64(value);
             }
         } else {
-            self.bytes.reserve(9);
-            self.bytes.put_u8(mask | 27);
-            self.bytes.put_u64(value);
+            self.output.reserve(9);
+            self.output.put_u8(mask | 27);
+            self.output.put_u64(value);
         }
     }
     pub fn write_u8(&mut self, value: u8) {
         self.write_u64(value as u64)
     }
+    /// Values beyond 64 bits are written as bignum, see `write_i128`.
     pub fn write_i64(&mut self, value: i128) {
+        self.write_i128(value);
+    }
+    /// Values beyond 64 bits are written as positive bignum (tag 2).
+    pub fn write_u128(&mut self, value: u128) {
+        if value <= u64::MAX as u128 {
+            self.write_u64(value as u64);
+        } else {
+            self.write_tag(IanaTag::PositiveBigNum);
+            self.write_bignum_bytes(value);
+        }
+    }
+    /// Values beyond 64 bits are written as bignum (tag 2 or 3).
+    pub fn write_i128(&mut self, value: i128) {
         if value >= 0 {
-            let value = if value > u64::max_value() as i128 {
-                u64::max_value()
-            } else {
-                value as u64
-            };
-            self.write_u64(value);
+            self.write_u128(value as u128);
             return;
         }
-
-        let value = if (value + 1).abs() > u64::max_value() as i128 {
-            u64::max_value()
+        let value = !value as u128;
+        if value <= u64::MAX as u128 {
+            self.write_u64_internal(value as u64, 0b0010_0000);
         } else {
-            (value + 1).abs() as u64
-        };
-        self.write_u64_internal(value, 0b0010_0000);
+            self.write_tag(IanaTag::NegativeBigNum);
+            self.write_bignum_bytes(value);
+        }
+    }
+    fn write_bignum_bytes(&mut self, value: u128) {
+        let bytes = value.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        self.write_bytes(&bytes[start..]);
     }
     pub fn write_f64(&mut self, value: f64) {
-        self.bytes.reserve(9);
-        self.bytes.put_u8(0b1110_0000 | 27u8);
-        self.bytes.put_f64(value);
+        if self.deterministic {
+            return self.write_float_preferred(value);
+        }
+        self.output.reserve(9);
+        self.write_special_head(27);
+        self.output.put_f64(value);
     }
     pub fn write_f32(&mut self, value: f32) {
-        self.bytes.reserve(5);
-        self.bytes.put_u8(0b1110_0000 | 26u8);
-        self.bytes.put_f32(value);
+        if self.deterministic {
+            return self.write_float_preferred(value as f64);
+        }
+        self.output.reserve(5);
+        self.write_special_head(26);
+        self.output.put_f32(value);
+    }
+    /// Writes a half, single or double precision float, whichever is the shortest without loss.
+    ///
+    /// NaN is always written as the half precision quiet NaN `0xf97e00`.
+    pub fn write_float_preferred(&mut self, value: f64) {
+        if let Some(bits) = f64_to_f16(value) {
+            self.output.reserve(3);
+            self.write_special_head(25);
+            self.output.put_u16(bits);
+        } else if value as f32 as f64 == value {
+            self.output.reserve(5);
+            self.write_special_head(26);
+            self.output.put_f32(value as f32);
+        } else {
+            self.output.reserve(9);
+            self.write_special_head(27);
+            self.output.put_f64(value);
+        }
     }
     #[cfg(feature = "iana_numbers")]
     pub fn write_f16(&mut self, value: f16) {
-        self.bytes.reserve(3);
-        self.bytes.put_u8(0b1110_0000 | 25u8);
-        self.bytes.put_u16(value.to_bits());
+        self.output.reserve(3);
+        self.write_special_head(25);
+        self.output.put_u16(value.to_bits());
     }
     pub fn write_tag(&mut self, tag: IanaTag) {
         self.write_u64_internal(tag.to_tag(), 0b1100_0000);
@@ -179,44 +273,66 @@ impl Serializer {
                 self.write_array_def(array.len());
                 array.iter().for_each(|element| self.write_value(element));
             }
-            Value::Map(array) => {
-                self.write_map_def(array.len());
-                array.iter().for_each(|(key, value)| {
-                    self.write_value(key);
-                    self.write_value(value);
-                });
-            }
+            Value::Map(array) => self.write_map_entries(
+                array.iter(),
+                |serializer, (key, _)| serializer.write_value(key),
+                |serializer, (_, value)| serializer.write_value(value),
+            ),
             Value::Special(special) => match special {
                 ReducedSpecial::Undefined => self.write_undefined(),
                 ReducedSpecial::Null => self.write_null(),
                 ReducedSpecial::Break => self.write_break(),
             },
             Value::Bool(val) => self.write_bool(*val),
+            Value::Simple(val) => self.write_simple(*val),
         }
     }
 
     pub fn write_bool(&mut self, val: bool) {
         if val {
-            self.bytes.put_u8(0b1110_0000 | 21u8);
+            self.write_special_head(21);
         } else {
-            self.bytes.put_u8(0b1110_0000 | 20u8);
+            self.write_special_head(20);
         }
     }
     pub fn write_null(&mut self) {
-        self.bytes.put_u8(0b1110_0000 | 22u8);
+        self.write_special_head(22);
     }
     pub fn write_undefined(&mut self) {
-        self.bytes.put_u8(0b1110_0000 | 23u8);
+        self.write_special_head(23);
+    }
+    fn write_special_head(&mut self, info: u8) {
+        let argument = if info == 31 { None } else { Some(info as u64) };
+        self.track_head(0b1110_0000, argument);
+        self.output.put_u8(0b1110_0000 | info);
     }
+    /// Fails the serializer in deterministic mode, which has no indefinite items to end.
     pub fn write_break(&mut self) {
-        self.bytes.put_u8(0b1110_0000 | 31u8);
+        if self.deterministic {
+            self.output.fail(CborError::UnexpectedBreak);
+        } else {
+            self.write_special_head(31);
+        }
+    }
+    /// Simple values 24 to 31 are reserved and can not be encoded, they fail the serializer.
+    pub fn write_simple(&mut self, val: u8) {
+        if (24..32).contains(&val) {
+            self.output.fail(CborError::InvalidSimpleValue(val));
+        } else if val < 24 {
+            self.write_special_head(val);
+        } else {
+            self.output.reserve(2);
+            self.write_special_head(24);
+            self.output.put_u8(val);
+        }
     }
 
+    /// The bytes written so far, empty when writing into a `BufMut` or `io::Write`.
     pub fn get_bytes(&self) -> &[u8] {
-        self.bytes.as_bytes()
+        self.output.as_bytes()
     }
     pub fn into_bytes(self) -> BytesMut {
-        self.bytes
+        self.output.into_bytes()
     }
 }
 
@@ -228,6 +344,14 @@ macro_rules! impl_pos_number {
             }
         }
     };
+    ($number:ty, $be:ident, $le:ident) => {
+        impl Serialize for $number {
+            fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+                serializer.write_u64(*self as u64);
+            }
+            impl_typed_array!($be, $le);
+        }
+    };
 }
 macro_rules! impl_neg_number {
     ($number:ty) => {
@@ -237,30 +361,103 @@ macro_rules! impl_neg_number {
             }
         }
     };
+    ($number:ty, $be:ident, $le:ident) => {
+        impl Serialize for $number {
+            fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+                serializer.write_i64(*self as i128);
+            }
+            impl_typed_array!($be, $le);
+        }
+    };
+}
+/// Overrides `serialize_slice` to write a typed array if `Context::typed_arrays` is set.
+macro_rules! impl_typed_array {
+    ($be:ident, $le:ident) => {
+        fn serialize_slice(slice: &[Self], serializer: &mut Serializer, context: &Context) {
+            #[cfg(feature = "iana_std")]
+            match context.typed_arrays {
+                Some(Endianness::Big) => return serializer.$be(slice),
+                Some(Endianness::Little) => return serializer.$le(slice),
+                None => {}
+            }
+            serializer.write_array_def(slice.len());
+            slice.iter().for_each(|e| e.serialize(serializer, context));
+        }
+    };
 }
 
 impl_pos_number!(usize);
-impl_pos_number!(u64);
-impl_pos_number!(u32);
-impl_pos_number!(u16);
+impl_pos_number!(u64, write_u64_array, write_u64_le_array);
+impl_pos_number!(u32, write_u32_array, write_u32_le_array);
+impl_pos_number!(u16, write_u16_array, write_u16_le_array);
 impl_neg_number!(isize);
-impl_neg_number!(i8);
-impl_neg_number!(i16);
-impl_neg_number!(i32);
-impl_neg_number!(i64);
+impl_neg_number!(i8, write_i8_array, write_i8_array);
+impl_neg_number!(i16, write_i16_array, write_i16_le_array);
+impl_neg_number!(i32, write_i32_array, write_i32_le_array);
+impl_neg_number!(i64, write_i64_array, write_i64_le_array);
 
-impl Serialize for f32 {
+impl Serialize for u8 {
     fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
-        serializer.write_f32(*self);
+        serializer.write_u8(*self);
+    }
+
+    fn serialize_slice(slice: &[Self], serializer: &mut Serializer, _context: &Context) {
+        serializer.write_bytes(slice);
     }
 }
 
-impl Serialize for f64 {
+impl Serialize for u128 {
     fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
-        serializer.write_f64(*self);
+        serializer.write_u128(*self);
     }
 }
 
+impl Serialize for i128 {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_i128(*self);
+    }
+}
+
+impl Serialize for bool {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_bool(*self);
+    }
+}
+
+impl Serialize for char {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_text(self.encode_utf8(&mut [0u8; 4]));
+    }
+}
+
+impl Serialize for () {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_null();
+    }
+}
+
+impl Serialize for f32 {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        match context.float_encoding {
+            _ if context.canonical => serializer.write_float_preferred(*self as f64),
+            FloatEncoding::Preferred => serializer.write_float_preferred(*self as f64),
+            FloatEncoding::Declared => serializer.write_f32(*self),
+        }
+    }
+    impl_typed_array!(write_f32_array, write_f32_le_array);
+}
+
+impl Serialize for f64 {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        match context.float_encoding {
+            _ if context.canonical => serializer.write_float_preferred(*self),
+            FloatEncoding::Preferred => serializer.write_float_preferred(*self),
+            FloatEncoding::Declared => serializer.write_f64(*self),
+        }
+    }
+    impl_typed_array!(write_f64_array, write_f64_le_array);
+}
+
 #[cfg(feature = "iana_numbers")]
 impl Serialize for f16 {
     fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
@@ -270,47 +467,62 @@ impl Serialize for f16 {
 
 impl<T: Serialize> Serialize for Vec<T> {
     fn serialize(&self, serializer: &mut Serializer, context: &Context) {
-        serializer.write_array_def(self.len());
-        self.iter().for_each(|e| e.serialize(serializer, context));
+        T::serialize_slice(self, serializer, context);
     }
 }
 
 impl<T: Serialize> Serialize for &[T] {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        T::serialize_slice(self, serializer, context);
+    }
+}
+
+impl<T: Serialize, const N: usize> Serialize for [T; N] {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        T::serialize_slice(self, serializer, context);
+    }
+}
+
+impl<T: Serialize> Serialize for VecDeque<T> {
     fn serialize(&self, serializer: &mut Serializer, context: &Context) {
         serializer.write_array_def(self.len());
         self.iter().for_each(|e| e.serialize(serializer, context));
     }
 }
 
-impl<K: Serialize, V: Serialize> Serialize for HashMap<K, V> {
+impl<T: Serialize> Serialize for HashSet<T> {
     fn serialize(&self, serializer: &mut Serializer, context: &Context) {
-        serializer.write_map_def(self.len());
-        self.iter().for_each(|(k, v)| {
-            k.serialize(serializer, context);
-            v.serialize(serializer, context);
-        });
+        serializer.write_array_def(self.len());
+        self.iter().for_each(|e| e.serialize(serializer, context));
     }
 }
 
-impl<K: Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
+impl<T: Serialize> Serialize for BTreeSet<T> {
     fn serialize(&self, serializer: &mut Serializer, context: &Context) {
-        serializer.write_map_def(self.len());
-        self.iter().for_each(|(k, v)| {
-            k.serialize(serializer, context);
-            v.serialize(serializer, context);
-        });
+        serializer.write_array_def(self.len());
+        self.iter().for_each(|e| e.serialize(serializer, context));
     }
 }
 
-impl Serialize for Vec<u8> {
-    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
-        serializer.write_bytes(self.as_slice());
+impl<K: Serialize, V: Serialize> Serialize for HashMap<K, V> {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        serializer.write_map_entries_with_context(
+            context,
+            self.iter(),
+            |serializer, (k, _)| k.serialize(serializer, context),
+            |serializer, (_, v)| v.serialize(serializer, context),
+        );
     }
 }
 
-impl Serialize for &[u8] {
-    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
-        serializer.write_bytes(self);
+impl<K: Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        serializer.write_map_entries_with_context(
+            context,
+            self.iter(),
+            |serializer, (k, _)| k.serialize(serializer, context),
+            |serializer, (_, v)| v.serialize(serializer, context),
+        );
     }
 }
 
@@ -330,7 +542,10 @@ impl<T: Serialize> Serialize for Option<T> {
     fn serialize(&self, serializer: &mut Serializer, context: &Context) {
         match self {
             Some(val) => val.serialize(serializer, context),
-            None => (),
+            None => match context.option_encoding {
+                OptionEncoding::Null => serializer.write_null(),
+                OptionEncoding::Undefined => serializer.write_undefined(),
+            },
         }
     }
 }
@@ -346,3 +561,49 @@ impl<T: Serialize> Serialize for Rc<T> {
         self.as_ref().serialize(serializer, context)
     }
 }
+
+impl<T: Serialize> Serialize for Box<T> {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        self.as_ref().serialize(serializer, context)
+    }
+}
+
+impl<T: Serialize> Serialize for &T {
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        (*self).serialize(serializer, context)
+    }
+}
+
+impl<T: ToOwned + ?Sized> Serialize for Cow<'_, T>
+where
+    for<'b> &'b T: Serialize,
+{
+    fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+        let borrowed: &T = self.as_ref();
+        Serialize::serialize(&borrowed, serializer, context)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($length:expr => $($name:ident $index:tt),+) => {
+        impl<$($name: Serialize),+> Serialize for ($($name,)+) {
+            fn serialize(&self, serializer: &mut Serializer, context: &Context) {
+                serializer.write_array_def($length);
+                $(self.$index.serialize(serializer, context);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(1 => A 0);
+impl_tuple!(2 => A 0, B 1);
+impl_tuple!(3 => A 0, B 1, C 2);
+impl_tuple!(4 => A 0, B 1, C 2, D 3);
+impl_tuple!(5 => A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6 => A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7 => A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(9 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(10 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(11 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(12 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -0,0 +1,171 @@
+use std::io::Write;
+
+use bytes::{BufMut, BytesMut};
+
+use crate::error::CborError;
+use crate::ser::Serializer;
+
+pub(crate) enum Sink<'a> {
+    Bytes(BytesMut),
+    Buf(Box<dyn BufMut + 'a>),
+    Writer(Box<dyn Write + 'a>),
+    Slice(&'a mut [u8]),
+}
+
+/// Where a `Serializer` writes to, remembers the first error so the `write_*` methods stay infallible.
+pub(crate) struct Output<'a> {
+    sink: Sink<'a>,
+    written: usize,
+    error: Option<CborError>,
+}
+
+macro_rules! put_number {
+    ($($be:ident, $le:ident, $number:ty;)*) => {
+        $(
+            pub(crate) fn $be(&mut self, value: $number) {
+                self.put_slice(&value.to_be_bytes());
+            }
+            pub(crate) fn $le(&mut self, value: $number) {
+                self.put_slice(&value.to_le_bytes());
+            }
+        )*
+    };
+}
+
+// which writers are used depends on the enabled features and the target endianness
+#[allow(dead_code)]
+impl<'a> Output<'a> {
+    pub(crate) fn new(sink: Sink<'a>) -> Self {
+        Self {
+            sink,
+            written: 0,
+            error: None,
+        }
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        if let Sink::Bytes(bytes) = &mut self.sink {
+            bytes.reserve(additional);
+        }
+    }
+    pub(crate) fn put_slice(&mut self, src: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+        let result = match &mut self.sink {
+            Sink::Bytes(bytes) => {
+                bytes.put_slice(src);
+                Ok(())
+            }
+            Sink::Buf(buf) if buf.remaining_mut() < src.len() => Err(CborError::BufferOverflow(
+                self.written + buf.remaining_mut(),
+            )),
+            Sink::Buf(buf) => {
+                buf.put_slice(src);
+                Ok(())
+            }
+            Sink::Writer(writer) => writer.write_all(src).map_err(CborError::from),
+            Sink::Slice(slice) => match slice.get_mut(self.written..self.written + src.len()) {
+                Some(target) => {
+                    target.copy_from_slice(src);
+                    Ok(())
+                }
+                None => Err(CborError::BufferOverflow(slice.len())),
+            },
+        };
+        match result {
+            Ok(()) => self.written += src.len(),
+            Err(e) => self.error = Some(e),
+        }
+    }
+    pub(crate) fn put_u8(&mut self, value: u8) {
+        self.put_slice(&[value]);
+    }
+    pub(crate) fn put_i8(&mut self, value: i8) {
+        self.put_slice(&value.to_be_bytes());
+    }
+    put_number! {
+        put_u16, put_u16_le, u16;
+        put_u32, put_u32_le, u32;
+        put_u64, put_u64_le, u64;
+        put_i16, put_i16_le, i16;
+        put_i32, put_i32_le, i32;
+        put_i64, put_i64_le, i64;
+        put_f32, put_f32_le, f32;
+        put_f64, put_f64_le, f64;
+    }
+
+    /// Keeps `error` unless an earlier one occurred.
+    pub(crate) fn fail(&mut self, error: CborError) {
+        self.error.get_or_insert(error);
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<CborError> {
+        self.error.take()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.written
+    }
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        match &self.sink {
+            Sink::Bytes(bytes) => bytes.as_ref(),
+            Sink::Slice(slice) => &slice[..self.written],
+            Sink::Buf(_) | Sink::Writer(_) => &[],
+        }
+    }
+    pub(crate) fn clear(&mut self) {
+        if let Sink::Bytes(bytes) = &mut self.sink {
+            bytes.clear();
+        }
+        if let Sink::Bytes(_) | Sink::Slice(_) = self.sink {
+            self.written = 0;
+            self.error = None;
+        }
+    }
+    pub(crate) fn into_bytes(self) -> BytesMut {
+        match self.sink {
+            Sink::Bytes(bytes) => bytes,
+            Sink::Slice(slice) => BytesMut::from(&slice[..self.written]),
+            Sink::Buf(_) | Sink::Writer(_) => BytesMut::new(),
+        }
+    }
+}
+
+impl<'a> Serializer<'a> {
+    /// Writes straight into an `io::Write` like a `File` or `TcpStream`, wrap those in a `BufWriter`.
+    ///
+    /// Errors are kept until `finish`, later writes are skipped.
+    pub fn from_writer<W: Write + 'a>(writer: W) -> Self {
+        Self::with_output(Output::new(Sink::Writer(Box::new(writer))))
+    }
+    /// Writes into any `BufMut`, e.g. a `&mut Vec<u8>`, running out of space is an error.
+    pub fn from_buf_mut<B: BufMut + 'a>(buf: B) -> Self {
+        Self::with_output(Output::new(Sink::Buf(Box::new(buf))))
+    }
+    /// Writes into a caller owned slice, running out of space is an error.
+    pub fn from_slice(slice: &'a mut [u8]) -> Self {
+        Self::with_output(Output::new(Sink::Slice(slice)))
+    }
+
+    /// Number of bytes written so far.
+    pub fn written(&self) -> usize {
+        self.output.len()
+    }
+    /// The first error of the underlying sink, like an io error or a full buffer.
+    pub fn error(&self) -> Option<&CborError> {
+        self.output.error.as_ref()
+    }
+    /// Flushes a writer and returns the number of bytes written or the first error.
+    pub fn finish(mut self) -> Result<usize, CborError> {
+        self.check_complete();
+        let mut output = self.output;
+        if let (None, Sink::Writer(writer)) = (&output.error, &mut output.sink) {
+            writer.flush()?;
+        }
+        match output.error {
+            Some(e) => Err(e),
+            None => Ok(output.written),
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use serde::ser::{self, Serialize};
+
+use crate::error::CborError;
+use crate::ser::builder::Items;
+use crate::ser::Serializer;
+
+/// Writes serde's data model as cbor.
+///
+/// Structs become maps keyed by field name and enums are externally tagged like in serde_json:
+/// unit variants are their name, other variants a map of the name to the content.
+/// Sequences and maps of unknown length are buffered to write a definite length,
+/// just like maps in deterministic mode to sort their entries.
+impl<'s, 'a> ser::Serializer for &'s mut Serializer<'a> {
+    type Ok = ();
+    type Error = CborError;
+    type SerializeSeq = SerdeCompound<'s, 'a>;
+    type SerializeTuple = SerdeCompound<'s, 'a>;
+    type SerializeTupleStruct = SerdeCompound<'s, 'a>;
+    type SerializeTupleVariant = SerdeCompound<'s, 'a>;
+    type SerializeMap = SerdeCompound<'s, 'a>;
+    type SerializeStruct = SerdeCompound<'s, 'a>;
+    type SerializeStructVariant = SerdeCompound<'s, 'a>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), CborError> {
+        self.write_bool(v);
+        Ok(())
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), CborError> {
+        self.serialize_i128(v as i128)
+    }
+    fn serialize_i16(self, v: i16) -> Result<(), CborError> {
+        self.serialize_i128(v as i128)
+    }
+    fn serialize_i32(self, v: i32) -> Result<(), CborError> {
+        self.serialize_i128(v as i128)
+    }
+    fn serialize_i64(self, v: i64) -> Result<(), CborError> {
+        self.serialize_i128(v as i128)
+    }
+    fn serialize_i128(self, v: i128) -> Result<(), CborError> {
+        self.write_i128(v);
+        Ok(())
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), CborError> {
+        self.serialize_u128(v as u128)
+    }
+    fn serialize_u16(self, v: u16) -> Result<(), CborError> {
+        self.serialize_u128(v as u128)
+    }
+    fn serialize_u32(self, v: u32) -> Result<(), CborError> {
+        self.serialize_u128(v as u128)
+    }
+    fn serialize_u64(self, v: u64) -> Result<(), CborError> {
+        self.serialize_u128(v as u128)
+    }
+    fn serialize_u128(self, v: u128) -> Result<(), CborError> {
+        self.write_u128(v);
+        Ok(())
+    }
+    fn serialize_f32(self, v: f32) -> Result<(), CborError> {
+        self.write_f32(v);
+        Ok(())
+    }
+    fn serialize_f64(self, v: f64) -> Result<(), CborError> {
+        self.write_f64(v);
+        Ok(())
+    }
+    fn serialize_char(self, v: char) -> Result<(), CborError> {
+        self.write_text(v.encode_utf8(&mut [0u8; 4]));
+        Ok(())
+    }
+    fn serialize_str(self, v: &str) -> Result<(), CborError> {
+        self.write_text(v);
+        Ok(())
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), CborError> {
+        self.write_bytes(v);
+        Ok(())
+    }
+    fn serialize_none(self) -> Result<(), CborError> {
+        self.write_null();
+        Ok(())
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), CborError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), CborError> {
+        self.write_null();
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), CborError> {
+        self.serialize_unit()
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), CborError> {
+        self.serialize_str(variant)
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), CborError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), CborError> {
+        self.write_map_def(1);
+        self.write_text(variant);
+        value.serialize(self)
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        Ok(SerdeCompound::array(self, len))
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        Ok(SerdeCompound::array(self, Some(len)))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        Ok(SerdeCompound::array(self, Some(len)))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        self.write_map_def(1);
+        self.write_text(variant);
+        Ok(SerdeCompound::array(self, Some(len)))
+    }
+    fn serialize_map(self, len: Option<usize>) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        Ok(SerdeCompound::map(self, len))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        Ok(SerdeCompound::map(self, Some(len)))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerdeCompound<'s, 'a>, CborError> {
+        self.write_map_def(1);
+        self.write_text(variant);
+        Ok(SerdeCompound::map(self, Some(len)))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+/// Serializes the items of arrays and maps, see `ser::Serializer for &mut Serializer`.
+pub struct SerdeCompound<'s, 'a> {
+    serializer: &'s mut Serializer<'a>,
+    /// Set while the items are buffered.
+    items: Option<Items<'a>>,
+}
+
+impl<'s, 'a> SerdeCompound<'s, 'a> {
+    fn array(serializer: &'s mut Serializer<'a>, len: Option<usize>) -> Self {
+        let items = match len {
+            Some(len) => {
+                serializer.write_array_def(len);
+                None
+            }
+            None => Some(serializer.begin_items()),
+        };
+        Self { serializer, items }
+    }
+    fn map(serializer: &'s mut Serializer<'a>, len: Option<usize>) -> Self {
+        let items = match len {
+            Some(len) if !serializer.is_deterministic() => {
+                serializer.write_map_def(len);
+                None
+            }
+            _ => Some(serializer.begin_items()),
+        };
+        Self { serializer, items }
+    }
+    fn item<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        value.serialize(&mut *self.serializer)
+    }
+    fn end_array(mut self) -> Result<(), CborError> {
+        if let Some(items) = self.items.take() {
+            self.serializer.end_array(items);
+        }
+        Ok(())
+    }
+    fn end_map(mut self) -> Result<(), CborError> {
+        if let Some(items) = self.items.take() {
+            self.serializer.end_map(items);
+        }
+        Ok(())
+    }
+}
+
+/// Restores the output if the compound is dropped before `end`, e.g. after a failed item.
+impl Drop for SerdeCompound<'_, '_> {
+    fn drop(&mut self) {
+        if let Some(items) = self.items.take() {
+            self.serializer.abort_items(items);
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_array()
+    }
+}
+
+impl ser::SerializeTuple for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_array()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_array()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_array()
+    }
+}
+
+impl ser::SerializeMap for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), CborError> {
+        self.item(key)
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), CborError> {
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_map()
+    }
+}
+
+impl ser::SerializeStruct for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CborError> {
+        self.serializer.write_text(key);
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_map()
+    }
+}
+
+impl ser::SerializeStructVariant for SerdeCompound<'_, '_> {
+    type Ok = ();
+    type Error = CborError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), CborError> {
+        self.serializer.write_text(key);
+        self.item(value)
+    }
+    fn end(self) -> Result<(), CborError> {
+        self.end_map()
+    }
+}
@@ -0,0 +1,55 @@
+//! Half precision conversions, available without the `half` crate.
+
+/// Decodes the bits of an IEEE 754 half precision float.
+pub(crate) fn f16_to_f64(bits: u16) -> f64 {
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as f64;
+    let value = match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        0x1f if mantissa == 0.0 => f64::INFINITY,
+        0x1f => f64::NAN,
+        _ => (mantissa + 1024.0) * 2f64.powi(exponent as i32 - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Returns the half precision bits if `value` can be represented without loss, NaN becomes `0x7e00`.
+pub(crate) fn f64_to_f16(value: f64) -> Option<u16> {
+    if value.is_nan() {
+        return Some(0x7e00);
+    }
+    let sign = if value.is_sign_negative() { 0x8000 } else { 0 };
+    let value = value.abs();
+    if value == 0.0 {
+        return Some(sign);
+    }
+    if value.is_infinite() {
+        return Some(sign | 0x7c00);
+    }
+    let single = value as f32;
+    if single as f64 != value {
+        return None;
+    }
+    let bits = single.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = bits & 0x7f_ffff;
+    if (-14..=15).contains(&exponent) {
+        if mantissa & 0x1fff != 0 {
+            return None;
+        }
+        Some(sign | ((exponent + 15) as u16) << 10 | (mantissa >> 13) as u16)
+    } else if (-24..-14).contains(&exponent) {
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (-1 - exponent) as u32;
+        if mantissa & ((1 << shift) - 1) != 0 {
+            return None;
+        }
+        Some(sign | (mantissa >> shift) as u16)
+    } else {
+        None
+    }
+}
@@ -1,12 +1,12 @@
 #![allow(clippy::len_without_is_empty)]
-use nom::number::complete::{be_u16, be_u64, be_u8};
-use nom::number::streaming::be_u32;
+use nom::number::streaming::{be_u16, be_u32, be_u64, be_u8};
 
 pub use iana_tag::*;
 
 use crate::de::Remaining;
 use crate::error::CborError;
 
+pub(crate) mod float;
 mod iana_tag;
 
 pub const MAX_INLINE_ENCODING: u8 = 23;
@@ -170,22 +170,16 @@ pub enum Special {
     Bool(bool),
     Null,
     Undefined,
-    #[cfg(feature = "iana_numbers")]
     F16,
     F32,
     F64,
     Break,
+    Simple(u8),
 }
 
 impl Special {
     pub fn is_float(&self) -> bool {
-        match self {
-            #[cfg(feature = "iana_numbers")]
-            Special::F16 => true,
-            Special::F32 => true,
-            Special::F64 => true,
-            _ => false,
-        }
+        matches!(self, Special::F16 | Special::F32 | Special::F64)
     }
     pub fn to_byte(&self) -> u8 {
         match self {
@@ -198,20 +192,26 @@ impl Special {
             }
             Special::Null => 22,
             Special::Undefined => 23,
-            #[cfg(feature = "iana_numbers")]
             Special::F16 => 25,
             Special::F32 => 26,
             Special::F64 => 27,
             Special::Break => 31,
+            Special::Simple(val) => {
+                if *val < 24 {
+                    *val
+                } else {
+                    24
+                }
+            }
         }
     }
     pub fn from_byte(byte: u8) -> Result<Self, CborError> {
         match byte {
+            0..=19 => Ok(Special::Simple(byte)),
             20 => Ok(Special::Bool(false)),
             21 => Ok(Special::Bool(true)),
             22 => Ok(Special::Null),
             23 => Ok(Special::Undefined),
-            #[cfg(feature = "iana_numbers")]
             25 => Ok(Special::F16),
             26 => Ok(Special::F32),
             27 => Ok(Special::F64),
@@ -276,6 +276,8 @@ impl Type {
                 0b1110_0000 => Ok(Type::Special(Special::from_byte(additional)?)),
                 _ => unreachable!(),
             }
+        } else if (28..=30).contains(&additional) {
+            Err(CborError::ReservedAdditionalInfo(byte))
         } else {
             let length = if additional == 31 {
                 Length::Indefinite
@@ -284,6 +286,9 @@ impl Type {
                 Length::AdditionalBytes(byte_size)
             };
             match byte & 0b1110_0000 {
+                0b0000_0000 | 0b0010_0000 | 0b1100_0000 if additional == 31 => {
+                    Err(CborError::InvalidIndefiniteLength(byte))
+                }
                 0b0000_0000 => Ok(Type::UnsignedInteger(Integer::Sized(ByteSize::from_byte(
                     additional,
                 )?))),
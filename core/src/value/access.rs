@@ -0,0 +1,125 @@
+use std::ops::Index;
+
+use crate::types::IanaTag;
+use crate::value::Value;
+use crate::ReducedSpecial;
+
+impl<'a> Value<'a> {
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::U64(val) => Some(*val),
+            _ => None,
+        }
+    }
+    /// Also returns unsigned values, every cbor integer fits an i128.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::U64(val) => Some(*val as i128),
+            Value::I128(val) => Some(*val),
+            _ => None,
+        }
+    }
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::F64(val) => Some(*val),
+            _ => None,
+        }
+    }
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(val) => Some(*val),
+            _ => None,
+        }
+    }
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&[Value<'a>]> {
+        match self {
+            Value::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value<'a>>> {
+        match self {
+            Value::Array(vec) => Some(vec),
+            _ => None,
+        }
+    }
+    pub fn as_map(&self) -> Option<&[(Value<'a>, Value<'a>)]> {
+        match self {
+            Value::Map(vec) => Some(vec),
+            _ => None,
+        }
+    }
+    pub fn as_map_mut(&mut self) -> Option<&mut Vec<(Value<'a>, Value<'a>)>> {
+        match self {
+            Value::Map(vec) => Some(vec),
+            _ => None,
+        }
+    }
+    pub fn as_tag(&self) -> Option<(IanaTag, &Value<'a>)> {
+        match self {
+            Value::Tag(tag, value) => Some((*tag, value)),
+            _ => None,
+        }
+    }
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Special(ReducedSpecial::Null))
+    }
+
+    /// Returns the array element at `index`.
+    pub fn get(&self, index: usize) -> Option<&Value<'a>> {
+        self.as_array().and_then(|vec| vec.get(index))
+    }
+    /// Returns the map value of the first entry whose key equals `key`.
+    pub fn get_key(&self, key: &Value) -> Option<&Value<'a>> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value)
+    }
+    pub fn get_text(&self, key: &str) -> Option<&Value<'a>> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_text() == Some(key))
+            .map(|(_, value)| value)
+    }
+    pub fn get_integer(&self, key: i128) -> Option<&Value<'a>> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_i128() == Some(key))
+            .map(|(_, value)| value)
+    }
+}
+
+impl<'a> Index<usize> for Value<'a> {
+    type Output = Value<'a>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.as_array() {
+            Some(vec) => &vec[index],
+            None => panic!("cannot index into {:?}", self),
+        }
+    }
+}
+
+impl<'a, 'k> Index<&'k str> for Value<'a> {
+    type Output = Value<'a>;
+
+    fn index(&self, key: &'k str) -> &Self::Output {
+        match self.get_text(key) {
+            Some(value) => value,
+            None => panic!("no entry {:?} in {:?}", key, self),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::value::Value;
+use crate::ReducedSpecial;
+
+/// Renders the value in diagnostic notation as described in RFC 8949 §8.
+///
+/// A `Value` does not remember indefinite lengths, use `Deserializer::take_diagnostic` to
+/// render those.
+impl<'a> Display for Value<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::U64(val) => write!(f, "{}", val),
+            Value::I128(val) => write!(f, "{}", val),
+            Value::F64(val) => write_float(f, *val),
+            Value::Bytes(bytes) => write_bytes(f, bytes),
+            Value::Text(text) => write_text(f, text),
+            Value::Bool(val) => write!(f, "{}", val),
+            Value::Array(vec) => {
+                f.write_char('[')?;
+                for (i, value) in vec.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            Value::Map(vec) => {
+                f.write_char('{')?;
+                for (i, (key, value)) in vec.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                f.write_char('}')
+            }
+            Value::Tag(tag, value) => write!(f, "{}({})", tag.to_tag(), value),
+            Value::Special(ReducedSpecial::Null) => f.write_str("null"),
+            Value::Special(ReducedSpecial::Undefined) => f.write_str("undefined"),
+            Value::Special(ReducedSpecial::Break) => f.write_str("break"),
+            Value::Simple(val) => write!(f, "simple({})", val),
+        }
+    }
+}
+
+pub(crate) fn write_float<W: Write>(f: &mut W, val: f64) -> fmt::Result {
+    if val.is_nan() {
+        return f.write_str("NaN");
+    }
+    if val.is_infinite() {
+        return f.write_str(if val > 0.0 { "Infinity" } else { "-Infinity" });
+    }
+    let debug = format!("{:?}", val);
+    match debug.find('e') {
+        Some(index) => {
+            let (mantissa, exponent) = (&debug[..index], &debug[index + 1..]);
+            f.write_str(mantissa)?;
+            if !mantissa.contains('.') {
+                f.write_str(".0")?;
+            }
+            if exponent.starts_with('-') {
+                write!(f, "e{}", exponent)
+            } else {
+                write!(f, "e+{}", exponent)
+            }
+        }
+        None => f.write_str(&debug),
+    }
+}
+
+pub(crate) fn write_bytes<W: Write>(f: &mut W, bytes: &[u8]) -> fmt::Result {
+    f.write_str("h'")?;
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    f.write_char('\'')
+}
+
+pub(crate) fn write_text<W: Write>(f: &mut W, text: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
@@ -0,0 +1,431 @@
+use std::borrow::Cow;
+
+use crate::error::CborError;
+use crate::types::IanaTag;
+use crate::value::Value;
+use crate::ReducedSpecial;
+
+const MAX_DEPTH: usize = 128;
+
+impl Value<'static> {
+    /// Parses (extended) diagnostic notation as written by `Display`.
+    ///
+    /// Supports integers (also `0x`, `0o`, `0b`), floats, `Infinity`, `NaN`, text with json
+    /// escapes, `h''` and `b64''` byte strings, arrays, maps, tags, `simple(n)`, indefinite
+    /// markers `_`, encoding indicators like `_1` and `/ block /` or `#` line comments.
+    pub fn from_diagnostic(input: &str) -> Result<Value<'static>, CborError> {
+        let mut parser = EdnParser {
+            input,
+            position: 0,
+            depth: 0,
+        };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace()?;
+        if parser.position < input.len() {
+            return Err(parser.error("unexpected trailing input"));
+        }
+        Ok(value)
+    }
+}
+
+struct EdnParser<'s> {
+    input: &'s str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'s> EdnParser<'s> {
+    fn error(&self, reason: &'static str) -> CborError {
+        error_at(self.position, reason)
+    }
+    fn rest(&self) -> &'s str {
+        &self.input[self.position..]
+    }
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+    fn eat(&mut self, prefix: &str) -> bool {
+        if self.rest().starts_with(prefix) {
+            self.position += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+    fn expect(&mut self, c: char, reason: &'static str) -> Result<(), CborError> {
+        self.skip_whitespace()?;
+        if self.eat(c.encode_utf8(&mut [0; 4])) {
+            Ok(())
+        } else {
+            Err(self.error(reason))
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), CborError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => match self.rest()[1..].find('/') {
+                    Some(end) => self.position += end + 2,
+                    None => return Err(self.error("unterminated comment")),
+                },
+                Some('#') => match self.rest().find('\n') {
+                    Some(end) => self.position += end + 1,
+                    None => self.position = self.input.len(),
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Skips an encoding indicator like `_1`, a lone `_` marks indefinite length.
+    fn skip_indicator(&mut self) -> bool {
+        if !self.eat("_") {
+            return false;
+        }
+        let digits = self
+            .rest()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        self.position += digits;
+        digits == 0
+    }
+
+    fn parse_value(&mut self) -> Result<Value<'static>, CborError> {
+        self.skip_whitespace()?;
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(CborError::LimitExceeded {
+                limit: "max_depth",
+                max: MAX_DEPTH,
+            });
+        }
+        let result = self.parse_item();
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_item(&mut self) -> Result<Value<'static>, CborError> {
+        let c = self.peek().ok_or_else(|| self.error("expected value"))?;
+        let start = self.position;
+        match c {
+            '[' => {
+                self.bump();
+                self.skip_whitespace()?;
+                self.skip_indicator();
+                let mut vec = Vec::new();
+                self.parse_sequence(']', |parser| {
+                    vec.push(parser.parse_value()?);
+                    Ok(())
+                })?;
+                Ok(Value::Array(vec))
+            }
+            '{' => {
+                self.bump();
+                self.skip_whitespace()?;
+                self.skip_indicator();
+                let mut vec = Vec::new();
+                self.parse_sequence('}', |parser| {
+                    let key = parser.parse_value()?;
+                    parser.expect(':', "expected ':' after map key")?;
+                    let value = parser.parse_value()?;
+                    vec.push((key, value));
+                    Ok(())
+                })?;
+                Ok(Value::Map(vec))
+            }
+            '(' => {
+                self.bump();
+                self.skip_whitespace()?;
+                if !self.skip_indicator() {
+                    return Err(self.error("expected '_' for indefinite string"));
+                }
+                let mut chunks = Vec::new();
+                self.parse_sequence(')', |parser| {
+                    chunks.push(parser.parse_value()?);
+                    Ok(())
+                })?;
+                concat_chunks(chunks)
+                    .ok_or_else(|| error_at(start, "mixed chunks in indefinite string"))
+            }
+            '"' => {
+                let text = self.parse_text()?;
+                self.skip_indicator();
+                Ok(Value::Text(Cow::Owned(text)))
+            }
+            '\'' => {
+                let bytes = self.parse_quoted()?.as_bytes().to_vec();
+                self.skip_indicator();
+                Ok(Value::Bytes(Cow::Owned(bytes)))
+            }
+            _ if self.eat("h'") => {
+                self.position -= 1;
+                let hex = self.parse_quoted()?;
+                let bytes = decode_hex(hex).ok_or_else(|| error_at(start, "invalid hex"))?;
+                self.skip_indicator();
+                Ok(Value::Bytes(Cow::Owned(bytes)))
+            }
+            _ if self.eat("b64'") => {
+                self.position -= 1;
+                let base64 = self.parse_quoted()?;
+                let bytes =
+                    decode_base64(base64).ok_or_else(|| error_at(start, "invalid base64"))?;
+                self.skip_indicator();
+                Ok(Value::Bytes(Cow::Owned(bytes)))
+            }
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            _ if self.eat("null") => Ok(Value::Special(ReducedSpecial::Null)),
+            _ if self.eat("undefined") => Ok(Value::Special(ReducedSpecial::Undefined)),
+            _ if self.eat("simple(") => {
+                let value = self.parse_number()?;
+                self.expect(')', "expected ')' after simple value")?;
+                match value {
+                    Value::U64(val) if val < 24 || (32..=255).contains(&val) => {
+                        Ok(Value::Simple(val as u8))
+                    }
+                    _ => Err(self.error("invalid simple value")),
+                }
+            }
+            _ => {
+                let number = self.parse_number()?;
+                if let Value::U64(tag) = number {
+                    if self.eat("(") {
+                        let value = self.parse_value()?;
+                        self.expect(')', "expected ')' after tag content")?;
+                        return Ok(Value::Tag(IanaTag::from_tag(tag), Box::new(value)));
+                    }
+                }
+                Ok(number)
+            }
+        }
+    }
+
+    fn parse_sequence<F>(&mut self, close: char, mut element: F) -> Result<(), CborError>
+    where
+        F: FnMut(&mut Self) -> Result<(), CborError>,
+    {
+        self.skip_whitespace()?;
+        if self.eat(close.encode_utf8(&mut [0; 4])) {
+            return Ok(());
+        }
+        loop {
+            element(self)?;
+            self.skip_whitespace()?;
+            match self.bump() {
+                Some(',') => {}
+                Some(c) if c == close => return Ok(()),
+                _ => return Err(self.error("expected ',' or closing bracket")),
+            }
+        }
+    }
+
+    /// Returns the raw content between single quotes.
+    fn parse_quoted(&mut self) -> Result<&'s str, CborError> {
+        self.bump();
+        let rest = self.rest();
+        match rest.find('\'') {
+            Some(end) => {
+                self.position += end + 1;
+                Ok(&rest[..end])
+            }
+            None => Err(self.error("unterminated byte string")),
+        }
+    }
+
+    fn parse_text(&mut self) -> Result<String, CborError> {
+        self.bump();
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let c = match self.bump() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.parse_unicode_escape()?,
+                        _ => return Err(self.error("invalid escape")),
+                    };
+                    text.push(c);
+                }
+                Some(c) => text.push(c),
+                None => return Err(self.error("unterminated text")),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char, CborError> {
+        let high = self.parse_hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.eat("\\u") {
+                return Err(self.error("expected low surrogate"));
+            }
+            let low = self.parse_hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error("invalid low surrogate"));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        std::char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, CborError> {
+        let hex = self
+            .rest()
+            .get(..4)
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+        let code =
+            u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid unicode escape"))?;
+        self.position += 4;
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<Value<'static>, CborError> {
+        self.skip_whitespace()?;
+        let negative = self.eat("-");
+        let value = if self.eat("Infinity") {
+            Value::F64(if negative {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            })
+        } else if !negative && self.eat("NaN") {
+            Value::F64(f64::NAN)
+        } else {
+            let radix = if self.eat("0x") {
+                16
+            } else if self.eat("0o") {
+                8
+            } else if self.eat("0b") {
+                2
+            } else {
+                10
+            };
+            let digits = self
+                .rest()
+                .find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)))
+                .unwrap_or(self.rest().len());
+            let mut literal = &self.rest()[..digits];
+            // only decimal numbers can have a fraction or exponent
+            if radix == 10 {
+                let end = literal
+                    .char_indices()
+                    .find(|&(i, c)| {
+                        !(c.is_ascii_digit()
+                            || c == '.'
+                            || c == 'e'
+                            || c == 'E'
+                            || ((c == '+' || c == '-')
+                                && i > 0
+                                && "eE".contains(&literal[i - 1..i])))
+                    })
+                    .map(|(i, _)| i)
+                    .unwrap_or(literal.len());
+                literal = &literal[..end];
+            } else {
+                let end = literal
+                    .find(|c: char| !c.is_digit(radix))
+                    .unwrap_or(literal.len());
+                literal = &literal[..end];
+            }
+            if literal.is_empty() {
+                return Err(self.error("expected value"));
+            }
+            let value = if radix == 10 && literal.contains(|c| ".eE".contains(c)) {
+                let val: f64 = literal.parse().map_err(|_| self.error("invalid float"))?;
+                Value::F64(if negative { -val } else { val })
+            } else {
+                // negative integers go down to -2^64
+                let val = u128::from_str_radix(literal, radix)
+                    .ok()
+                    .filter(|val| *val <= u64::MAX as u128 + negative as u128)
+                    .ok_or_else(|| self.error("invalid integer"))?;
+                if negative && val != 0 {
+                    Value::I128(-(val as i128))
+                } else {
+                    Value::U64(val as u64)
+                }
+            };
+            self.position += literal.len();
+            value
+        };
+        self.skip_indicator();
+        Ok(value)
+    }
+}
+
+fn error_at(offset: usize, reason: &'static str) -> CborError {
+    CborError::InvalidDiagnostic { offset, reason }
+}
+
+fn concat_chunks(chunks: Vec<Value<'static>>) -> Option<Value<'static>> {
+    match chunks.first() {
+        Some(Value::Text(_)) => {
+            let mut text = String::new();
+            for chunk in chunks {
+                text.push_str(chunk.as_text()?);
+            }
+            Some(Value::Text(Cow::Owned(text)))
+        }
+        _ => {
+            let mut bytes = Vec::new();
+            for chunk in chunks {
+                bytes.extend_from_slice(chunk.as_bytes()?);
+            }
+            Some(Value::Bytes(Cow::Owned(bytes)))
+        }
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = hex
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<_>>()?;
+    let pairs = digits.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    Some(pairs.map(|pair| pair[0] << 4 | pair[1]).collect())
+}
+
+fn decode_base64(base64: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in base64.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' | '-' => 62,
+            '/' | '_' => 63,
+            _ => return None,
+        };
+        buffer = buffer << 6 | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
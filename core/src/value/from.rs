@@ -0,0 +1,127 @@
+use std::borrow::Cow;
+
+use crate::types::IanaTag;
+use crate::value::Value;
+use crate::ReducedSpecial;
+
+macro_rules! impl_from_unsigned {
+    ($number:ty) => {
+        impl<'a> From<$number> for Value<'a> {
+            fn from(val: $number) -> Self {
+                Value::U64(val as u64)
+            }
+        }
+    };
+}
+macro_rules! impl_from_signed {
+    ($number:ty) => {
+        impl<'a> From<$number> for Value<'a> {
+            fn from(val: $number) -> Self {
+                if val >= 0 {
+                    Value::U64(val as u64)
+                } else {
+                    Value::I128(val as i128)
+                }
+            }
+        }
+    };
+}
+
+impl_from_unsigned!(u8);
+impl_from_unsigned!(u16);
+impl_from_unsigned!(u32);
+impl_from_unsigned!(u64);
+impl_from_unsigned!(usize);
+
+impl_from_signed!(i8);
+impl_from_signed!(i16);
+impl_from_signed!(i32);
+impl_from_signed!(i64);
+impl_from_signed!(isize);
+
+impl<'a> From<i128> for Value<'a> {
+    fn from(val: i128) -> Self {
+        if val >= 0 && val <= u64::MAX as i128 {
+            Value::U64(val as u64)
+        } else {
+            Value::I128(val)
+        }
+    }
+}
+
+/// Values above `u64::MAX` become a positive bignum (tag 2).
+impl<'a> From<u128> for Value<'a> {
+    fn from(val: u128) -> Self {
+        if val <= u64::MAX as u128 {
+            return Value::U64(val as u64);
+        }
+        let bytes = val.to_be_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        let bytes = Value::Bytes(Cow::Owned(bytes[start..].to_vec()));
+        Value::Tag(IanaTag::PositiveBigNum, Box::new(bytes))
+    }
+}
+
+impl<'a> From<f32> for Value<'a> {
+    fn from(val: f32) -> Self {
+        Value::F64(val as f64)
+    }
+}
+impl<'a> From<f64> for Value<'a> {
+    fn from(val: f64) -> Self {
+        Value::F64(val)
+    }
+}
+impl<'a> From<bool> for Value<'a> {
+    fn from(val: bool) -> Self {
+        Value::Bool(val)
+    }
+}
+impl<'a> From<char> for Value<'a> {
+    fn from(val: char) -> Self {
+        Value::Text(Cow::Owned(val.to_string()))
+    }
+}
+impl<'a> From<&'a str> for Value<'a> {
+    fn from(val: &'a str) -> Self {
+        Value::Text(Cow::Borrowed(val))
+    }
+}
+impl<'a> From<String> for Value<'a> {
+    fn from(val: String) -> Self {
+        Value::Text(Cow::Owned(val))
+    }
+}
+impl<'a> From<&'a [u8]> for Value<'a> {
+    fn from(val: &'a [u8]) -> Self {
+        Value::Bytes(Cow::Borrowed(val))
+    }
+}
+impl<'a> From<Vec<u8>> for Value<'a> {
+    fn from(val: Vec<u8>) -> Self {
+        Value::Bytes(Cow::Owned(val))
+    }
+}
+impl<'a> From<Vec<Value<'a>>> for Value<'a> {
+    fn from(val: Vec<Value<'a>>) -> Self {
+        Value::Array(val)
+    }
+}
+impl<'a> From<Vec<(Value<'a>, Value<'a>)>> for Value<'a> {
+    fn from(val: Vec<(Value<'a>, Value<'a>)>) -> Self {
+        Value::Map(val)
+    }
+}
+impl<'a, T: Into<Value<'a>>> From<Option<T>> for Value<'a> {
+    fn from(val: Option<T>) -> Self {
+        match val {
+            Some(val) => val.into(),
+            None => Value::Special(ReducedSpecial::Null),
+        }
+    }
+}
+impl<'a> From<ReducedSpecial> for Value<'a> {
+    fn from(val: ReducedSpecial) -> Self {
+        Value::Special(val)
+    }
+}
@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+use serde_json::{Map, Number};
+
+use crate::error::CborError;
+use crate::types::IanaTag;
+use crate::value::Value;
+use crate::ReducedSpecial;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// How `Value::to_json` treats items json can not represent.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum JsonPolicy {
+    /// Converts as described in RFC 8949 §6.1, e.g. NaN becomes null and unknown tags are dropped.
+    #[default]
+    Lossy,
+    /// Fails with `NotJsonCompatible` instead of dropping information.
+    Strict,
+}
+
+#[derive(Copy, Clone)]
+enum BytesEncoding {
+    Base64Url,
+    Base64,
+    Base16,
+}
+
+impl<'a> Value<'a> {
+    /// Converts into json following RFC 8949 §6.1.
+    ///
+    /// Byte strings become base64url strings unless an enclosing tag 21, 22 or 23 expects
+    /// another encoding, bignums become base64url strings with a `~` prefix for negative ones.
+    pub fn to_json(&self, policy: JsonPolicy) -> Result<serde_json::Value, CborError> {
+        to_json(self, policy, BytesEncoding::Base64Url)
+    }
+
+    /// Converts json into cbor following RFC 8949 §6.2.
+    pub fn from_json(json: &serde_json::Value) -> Value<'static> {
+        match json {
+            serde_json::Value::Null => Value::Special(ReducedSpecial::Null),
+            serde_json::Value::Bool(val) => Value::Bool(*val),
+            serde_json::Value::Number(number) => {
+                if let Some(val) = number.as_u64() {
+                    Value::U64(val)
+                } else if let Some(val) = number.as_i64() {
+                    Value::I128(val as i128)
+                } else {
+                    Value::F64(number.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            serde_json::Value::String(text) => Value::Text(Cow::Owned(text.clone())),
+            serde_json::Value::Array(vec) => {
+                Value::Array(vec.iter().map(Value::from_json).collect())
+            }
+            serde_json::Value::Object(map) => Value::Map(
+                map.iter()
+                    .map(|(key, value)| {
+                        (
+                            Value::Text(Cow::Owned(key.clone())),
+                            Value::from_json(value),
+                        )
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<serde_json::Value> for Value<'static> {
+    fn from(json: serde_json::Value) -> Self {
+        Value::from_json(&json)
+    }
+}
+
+fn to_json(
+    value: &Value,
+    policy: JsonPolicy,
+    encoding: BytesEncoding,
+) -> Result<serde_json::Value, CborError> {
+    let json = match value {
+        Value::U64(val) => serde_json::Value::from(*val),
+        Value::I128(val) => {
+            if let Ok(val) = i64::try_from(*val) {
+                serde_json::Value::from(val)
+            } else if let Ok(val) = u64::try_from(*val) {
+                serde_json::Value::from(val)
+            } else {
+                return lossy(
+                    policy,
+                    serde_json::Value::from(*val as f64),
+                    "integer exceeds json range",
+                );
+            }
+        }
+        Value::F64(val) => match Number::from_f64(*val) {
+            Some(number) => serde_json::Value::Number(number),
+            None => return lossy(policy, serde_json::Value::Null, "NaN or Infinity"),
+        },
+        Value::Bytes(bytes) => serde_json::Value::String(encode_bytes(bytes, encoding)),
+        Value::Text(text) => serde_json::Value::String(text.to_string()),
+        Value::Bool(val) => serde_json::Value::Bool(*val),
+        Value::Array(vec) => serde_json::Value::Array(
+            vec.iter()
+                .map(|value| to_json(value, policy, encoding))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(vec) => {
+            let mut map = Map::with_capacity(vec.len());
+            for (key, value) in vec {
+                let key = match key {
+                    Value::Text(text) => text.to_string(),
+                    key => match to_json(key, policy, encoding)? {
+                        serde_json::Value::String(text) => lossy(policy, text, "non text map key")?,
+                        json => lossy(policy, json.to_string(), "non text map key")?,
+                    },
+                };
+                let value = to_json(value, policy, encoding)?;
+                if map.insert(key, value).is_some() && policy == JsonPolicy::Strict {
+                    return Err(CborError::NotJsonCompatible("duplicate map key"));
+                }
+            }
+            serde_json::Value::Object(map)
+        }
+        Value::Tag(tag, value) => match (tag, value.as_ref()) {
+            (IanaTag::PositiveBigNum, Value::Bytes(bytes)) => {
+                serde_json::Value::String(encode_bytes(bytes, BytesEncoding::Base64Url))
+            }
+            (IanaTag::NegativeBigNum, Value::Bytes(bytes)) => serde_json::Value::String(format!(
+                "~{}",
+                encode_bytes(bytes, BytesEncoding::Base64Url)
+            )),
+            (IanaTag::Base64UrlMultiple, value) => {
+                to_json(value, policy, BytesEncoding::Base64Url)?
+            }
+            (IanaTag::Base64Multiple, value) => to_json(value, policy, BytesEncoding::Base64)?,
+            (IanaTag::Base16Multiple, value) => to_json(value, policy, BytesEncoding::Base16)?,
+            (_, value) => {
+                let json = to_json(value, policy, encoding)?;
+                return lossy(policy, json, "tag");
+            }
+        },
+        Value::Special(ReducedSpecial::Null) => serde_json::Value::Null,
+        Value::Special(ReducedSpecial::Undefined) => {
+            return lossy(policy, serde_json::Value::Null, "undefined")
+        }
+        Value::Special(ReducedSpecial::Break) => {
+            return lossy(policy, serde_json::Value::Null, "break")
+        }
+        Value::Simple(_) => return lossy(policy, serde_json::Value::Null, "simple value"),
+    };
+    Ok(json)
+}
+
+/// Returns the lossy conversion unless the policy is strict.
+fn lossy<T>(policy: JsonPolicy, converted: T, reason: &'static str) -> Result<T, CborError> {
+    match policy {
+        JsonPolicy::Lossy => Ok(converted),
+        JsonPolicy::Strict => Err(CborError::NotJsonCompatible(reason)),
+    }
+}
+
+fn encode_bytes(bytes: &[u8], encoding: BytesEncoding) -> String {
+    match encoding {
+        BytesEncoding::Base64Url => encode_base64(bytes, BASE64_URL, false),
+        BytesEncoding::Base64 => encode_base64(bytes, BASE64, true),
+        BytesEncoding::Base16 => bytes.iter().map(|byte| format!("{:02x}", byte)).collect(),
+    }
+}
+
+fn encode_base64(bytes: &[u8], alphabet: &[u8; 64], padding: bool) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let buffer = chunk.iter().enumerate().fold(0u32, |buffer, (i, byte)| {
+            buffer | (*byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            encoded.push(alphabet[(buffer >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if padding {
+            for _ in chunk.len()..3 {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
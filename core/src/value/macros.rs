@@ -0,0 +1,69 @@
+/// Builds a `Value` from a literal.
+///
+/// Arrays are written as `[a, b]`, maps as `{key => value}`, tags as `tag(1, value)` and
+/// `null` / `undefined` become the matching specials. Everything else is converted with
+/// `Value::from`.
+///
+/// ```
+/// use cbor_enhanced::cbor;
+///
+/// let value = cbor!({"name" => "cbor", 1 => [1, -2, null], "time" => tag(1, 1363896240)});
+/// assert_eq!(value["name"].as_text(), Some("cbor"));
+/// ```
+#[macro_export]
+macro_rules! cbor {
+    (null) => {
+        $crate::Value::Special($crate::ReducedSpecial::Null)
+    };
+    (undefined) => {
+        $crate::Value::Special($crate::ReducedSpecial::Undefined)
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::Value::Array($crate::cbor!(@array [] () $($tt)*))
+    };
+    ({ $($tt:tt)* }) => {
+        $crate::Value::Map($crate::cbor!(@map [] () $($tt)*))
+    };
+    (tag ( $tag:expr, $($value:tt)+ )) => {
+        $crate::Value::Tag(
+            $crate::IanaTag::from_tag($tag),
+            ::std::boxed::Box::new($crate::cbor!($($value)+)),
+        )
+    };
+
+    (@array [$($done:expr,)*] ()) => {
+        vec![$($done,)*]
+    };
+    (@array [$($done:expr,)*] ($($element:tt)+)) => {
+        vec![$($done,)* $crate::cbor!($($element)+),]
+    };
+    (@array [$($done:expr,)*] ($($element:tt)+) , $($rest:tt)*) => {
+        $crate::cbor!(@array [$($done,)* $crate::cbor!($($element)+),] () $($rest)*)
+    };
+    (@array [$($done:expr,)*] ($($element:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::cbor!(@array [$($done,)*] ($($element)* $next) $($rest)*)
+    };
+
+    (@map [$($done:expr,)*] ()) => {
+        vec![$($done,)*]
+    };
+    (@map [$($done:expr,)*] ($($key:tt)+) => $($rest:tt)*) => {
+        $crate::cbor!(@entry [$($done,)*] ($($key)+) () $($rest)*)
+    };
+    (@map [$($done:expr,)*] ($($key:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::cbor!(@map [$($done,)*] ($($key)* $next) $($rest)*)
+    };
+    (@entry [$($done:expr,)*] ($($key:tt)+) ($($value:tt)+)) => {
+        vec![$($done,)* ($crate::cbor!($($key)+), $crate::cbor!($($value)+)),]
+    };
+    (@entry [$($done:expr,)*] ($($key:tt)+) ($($value:tt)+) , $($rest:tt)*) => {
+        $crate::cbor!(@map [$($done,)* ($crate::cbor!($($key)+), $crate::cbor!($($value)+)),] () $($rest)*)
+    };
+    (@entry [$($done:expr,)*] ($($key:tt)+) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::cbor!(@entry [$($done,)*] ($($key)+) ($($value)* $next) $($rest)*)
+    };
+
+    ($other:expr) => {
+        $crate::Value::from($other)
+    };
+}
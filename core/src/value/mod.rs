@@ -1,16 +1,151 @@
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+use crate::context::Context;
+use crate::de::{Deserialize, Deserializer};
+use crate::error::CborError;
+use crate::ser::{Serialize, Serializer};
 use crate::types::IanaTag;
 use crate::ReducedSpecial;
 
+#[cfg(feature = "json")]
+pub use json::JsonPolicy;
+pub use raw::RawValue;
+
+mod access;
+pub(crate) mod diagnostic;
+mod edn;
+mod from;
+#[cfg(feature = "json")]
+mod json;
+mod macros;
+mod raw;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value<'a> {
     U64(u64),
     I128(i128),
     F64(f64),
-    Bytes(&'a [u8]),
-    Text(&'a str),
+    Bytes(Cow<'a, [u8]>),
+    Text(Cow<'a, str>),
     Bool(bool),
     Array(Vec<Value<'a>>),
     Map(Vec<(Value<'a>, Value<'a>)>),
     Tag(IanaTag, Box<Value<'a>>),
     Special(ReducedSpecial),
+    Simple(u8),
+}
+
+/// A `Value` which does not borrow from the input, it can be decoded from any buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedValue(pub Value<'static>);
+
+impl<'a> Value<'a> {
+    /// Copies all borrowed bytes and strings so the value can outlive the input.
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::U64(val) => Value::U64(val),
+            Value::I128(val) => Value::I128(val),
+            Value::F64(val) => Value::F64(val),
+            Value::Bytes(bytes) => Value::Bytes(Cow::Owned(bytes.into_owned())),
+            Value::Text(text) => Value::Text(Cow::Owned(text.into_owned())),
+            Value::Bool(val) => Value::Bool(val),
+            Value::Array(vec) => Value::Array(vec.into_iter().map(Value::into_owned).collect()),
+            Value::Map(vec) => Value::Map(
+                vec.into_iter()
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect(),
+            ),
+            Value::Tag(tag, value) => Value::Tag(tag, Box::new(value.into_owned())),
+            Value::Special(special) => Value::Special(special),
+            Value::Simple(val) => Value::Simple(val),
+        }
+    }
+}
+
+impl Deref for OwnedValue {
+    type Target = Value<'static>;
+
+    fn deref(&self) -> &Value<'static> {
+        &self.0
+    }
+}
+
+impl DerefMut for OwnedValue {
+    fn deref_mut(&mut self) -> &mut Value<'static> {
+        &mut self.0
+    }
+}
+
+impl From<Value<'_>> for OwnedValue {
+    fn from(value: Value) -> Self {
+        OwnedValue(value.into_owned())
+    }
+}
+
+impl From<OwnedValue> for Value<'static> {
+    fn from(value: OwnedValue) -> Self {
+        value.0
+    }
+}
+
+/// Bytes and strings are borrowed from the input, decode an `OwnedValue` to copy them.
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_value(data)
+    }
+}
+
+impl Deserializer {
+    /// Like `take_value` but copies the borrowed bytes and strings, which count as allocated.
+    pub fn take_owned_value<'de>(
+        &self,
+        data: &'de [u8],
+    ) -> Result<(OwnedValue, &'de [u8]), CborError> {
+        let (value, remaining) = self.take_value(data)?;
+        self.reserve_allocation(borrowed_len(&value))?;
+        Ok((OwnedValue(value.into_owned()), remaining))
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedValue {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        deserializer.take_owned_value(data)
+    }
+}
+
+fn borrowed_len(value: &Value) -> usize {
+    match value {
+        Value::Bytes(Cow::Borrowed(bytes)) => bytes.len(),
+        Value::Text(Cow::Borrowed(text)) => text.len(),
+        Value::Array(vec) => vec.iter().map(borrowed_len).sum(),
+        Value::Map(vec) => vec
+            .iter()
+            .map(|(key, value)| borrowed_len(key) + borrowed_len(value))
+            .sum(),
+        Value::Tag(_, value) => borrowed_len(value),
+        _ => 0,
+    }
+}
+
+impl Serialize for Value<'_> {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_value(self);
+    }
+}
+
+impl Serialize for OwnedValue {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_value(&self.0);
+    }
 }
@@ -0,0 +1,62 @@
+use crate::context::Context;
+use crate::de::{Deserialize, Deserializer};
+use crate::error::CborError;
+use crate::ser::{Serialize, Serializer};
+use crate::value::Value;
+
+/// The encoded bytes of exactly one cbor item, borrowed from the input.
+///
+/// Can be decoded later or serialized again verbatim.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct RawValue<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> RawValue<'a> {
+    /// Fails unless `bytes` contains exactly one well formed item.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, CborError> {
+        let remaining = Deserializer::new().skip_value(bytes)?;
+        if remaining.is_empty() {
+            Ok(Self { bytes })
+        } else {
+            Err(CborError::TrailingBytes(remaining.len()))
+        }
+    }
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+    pub fn to_value(&self) -> Result<Value<'a>, CborError> {
+        Deserializer::new().take_value(self.bytes).map(|v| v.0)
+    }
+    pub fn deserialize_into<T: Deserialize<'a>>(
+        &self,
+        deserializer: &mut Deserializer,
+        context: &Context,
+    ) -> Result<T, CborError> {
+        deserializer.decode(self.bytes, context).map(|v| v.0)
+    }
+}
+
+impl<'a> AsRef<[u8]> for RawValue<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'de> Deserialize<'de> for RawValue<'de> {
+    fn deserialize(
+        deserializer: &mut Deserializer,
+        data: &'de [u8],
+        _context: &Context,
+    ) -> Result<(Self, &'de [u8]), CborError> {
+        let remaining = deserializer.skip_value(data)?;
+        let bytes = &data[..data.len() - remaining.len()];
+        Ok((RawValue { bytes }, remaining))
+    }
+}
+
+impl<'a> Serialize for RawValue<'a> {
+    fn serialize(&self, serializer: &mut Serializer, _context: &Context) {
+        serializer.write_raw(self.bytes);
+    }
+}
@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::de::{self, Deserialize, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq};
+
+use crate::types::IanaTag;
+use crate::value::{OwnedValue, Value};
+use crate::ReducedSpecial;
+
+/// Tags have no counterpart in serde, only their content is serialized.
+/// Bignums (tag 2 and 3) of up to 16 bytes become `u128` and `i128`.
+impl Serialize for Value<'_> {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::U64(value) => serializer.serialize_u64(*value),
+            Value::I128(value) => match i64::try_from(*value) {
+                Ok(value) => serializer.serialize_i64(value),
+                Err(_) => serializer.serialize_i128(*value),
+            },
+            Value::F64(value) => serializer.serialize_f64(*value),
+            Value::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            Value::Text(text) => serializer.serialize_str(text),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Array(vec) => {
+                let mut seq = serializer.serialize_seq(Some(vec.len()))?;
+                for value in vec {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Value::Map(vec) => {
+                let mut map = serializer.serialize_map(Some(vec.len()))?;
+                for (key, value) in vec {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+            Value::Tag(tag, value) => match (tag, value.as_ref()) {
+                (IanaTag::PositiveBigNum, Value::Bytes(bytes)) if bytes.len() <= 16 => {
+                    serializer.serialize_u128(bignum(bytes))
+                }
+                (IanaTag::NegativeBigNum, Value::Bytes(bytes)) if bytes.len() <= 16 => {
+                    match i128::try_from(bignum(bytes)) {
+                        Ok(value) => serializer.serialize_i128(-1 - value),
+                        Err(_) => Err(ser::Error::custom("Bignum does not fit into i128")),
+                    }
+                }
+                (_, value) => value.serialize(serializer),
+            },
+            Value::Special(ReducedSpecial::Break) => {
+                Err(ser::Error::custom("Break can not be serialized"))
+            }
+            Value::Special(_) => serializer.serialize_unit(),
+            Value::Simple(value) => Err(ser::Error::custom(format!(
+                "Simple value {} can not be serialized",
+                value
+            ))),
+        }
+    }
+}
+
+fn bignum(bytes: &[u8]) -> u128 {
+    bytes
+        .iter()
+        .fold(0u128, |value, byte| (value << 8) | *byte as u128)
+}
+
+/// Strings and bytes are borrowed if the deserializer hands them out borrowed.
+impl<'de: 'a, 'a> Deserialize<'de> for Value<'a> {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+impl Serialize for OwnedValue {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedValue {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(OwnedValue::from)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any cbor value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value<'de>, E> {
+        Ok(Value::Bool(v))
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value<'de>, E> {
+        self.visit_i128(v as i128)
+    }
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Value<'de>, E> {
+        match u64::try_from(v) {
+            Ok(v) => Ok(Value::U64(v)),
+            Err(_) => Ok(Value::I128(v)),
+        }
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value<'de>, E> {
+        Ok(Value::U64(v))
+    }
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Value<'de>, E> {
+        Ok(Value::from(v))
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value<'de>, E> {
+        Ok(Value::F64(v))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value<'de>, E> {
+        Ok(Value::Text(Cow::Owned(v.to_string())))
+    }
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Value<'de>, E> {
+        Ok(Value::Text(Cow::Borrowed(v)))
+    }
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value<'de>, E> {
+        Ok(Value::Text(Cow::Owned(v)))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value<'de>, E> {
+        Ok(Value::Bytes(Cow::Owned(v.to_vec())))
+    }
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Value<'de>, E> {
+        Ok(Value::Bytes(Cow::Borrowed(v)))
+    }
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Value<'de>, E> {
+        Ok(Value::Bytes(Cow::Owned(v)))
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Value<'de>, E> {
+        Ok(Value::Special(ReducedSpecial::Null))
+    }
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value<'de>, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Value<'de>, E> {
+        Ok(Value::Special(ReducedSpecial::Null))
+    }
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Value<'de>, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value<'de>, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(value) = seq.next_element()? {
+            vec.push(value);
+        }
+        Ok(Value::Array(vec))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value<'de>, A::Error> {
+        let mut vec = Vec::with_capacity(map.size_hint().unwrap_or(0).min(4096));
+        while let Some(entry) = map.next_entry()? {
+            vec.push(entry);
+        }
+        Ok(Value::Map(vec))
+    }
+}
@@ -0,0 +1,105 @@
+use cbor_enhanced::{CborError, IanaTag, LengthCheck, Serializer};
+
+#[test]
+fn test_array_and_map_builders() {
+    let mut serializer = Serializer::new();
+    serializer.map(|m| {
+        m.write_text("a");
+        m.array(|a| {
+            for i in 0..30 {
+                a.write_u64(i);
+            }
+        });
+        m.write_text("b");
+        m.write_tag(IanaTag::PositiveBigNum);
+        m.write_bytes(b"\x01");
+        m.write_text("c");
+        m.write_array_def(2);
+        m.write_u64(1);
+        m.array(|_| {});
+    });
+    let bytes = serializer.get_bytes();
+    assert_eq!(&bytes[..5], b"\xa3\x61\x61\x98\x1e");
+    assert_eq!(&bytes[41..], b"\x61\x62\xc2\x41\x01\x61\x63\x82\x01\x80");
+}
+
+#[test]
+fn test_deterministic_map_builder() {
+    let mut serializer = Serializer::deterministic();
+    serializer.map(|m| {
+        m.write_text("b");
+        m.write_f64(1.5);
+        m.write_text("a");
+        m.begin_text_chunked().write_chunk("x");
+    });
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\xa2\x61\x61\x61\x78\x61\x62\xf9\x3e\x00"
+    );
+
+    let mut serializer = Serializer::deterministic();
+    serializer.map(|m| {
+        m.write_u64(1);
+        m.write_u64(2);
+        m.write_u64(1);
+        m.write_u64(3);
+    });
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::DuplicateMapKey(_))
+    ));
+}
+
+#[test]
+fn test_length_check() {
+    let mut buffer = Vec::new();
+    let mut serializer = Serializer::from_writer(&mut buffer);
+    serializer.set_length_check(LengthCheck::Error);
+    serializer.write_array_def(2);
+    serializer.write_u64(1);
+    serializer.write_array_def(1);
+    serializer.write_u64(2);
+    assert!(serializer.error().is_none());
+    serializer.write_map_def(1);
+    serializer.write_u64(1);
+    assert!(matches!(
+        serializer.finish(),
+        Err(CborError::MissingItems(1))
+    ));
+
+    let mut serializer = Serializer::new();
+    serializer.set_length_check(LengthCheck::Error);
+    serializer.array(|a| {
+        a.write_array_def(3);
+        a.write_u64(1);
+    });
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::MissingItems(2))
+    ));
+
+    let mut serializer = Serializer::new();
+    serializer.set_length_check(LengthCheck::Error);
+    serializer.map(|m| m.write_u64(1));
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::MissingItems(1))
+    ));
+
+    let mut serializer = Serializer::new();
+    serializer.set_length_check(LengthCheck::Error);
+    serializer.write_array_def(1);
+    serializer.write_break();
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::UnexpectedBreak)
+    ));
+}
+
+#[test]
+#[should_panic(expected = "Indefinite length item is missing its break")]
+fn test_length_check_panic() {
+    let mut serializer = Serializer::new();
+    serializer.set_length_check(LengthCheck::Panic);
+    serializer.array(|a| a.begin_array_indefinite());
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use cbor_enhanced::{from_bytes, Context, OptionEncoding, Serialize, Serializer};
+
+fn to_bytes<T: Serialize>(value: &T, context: &Context) -> Vec<u8> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer, context);
+    serializer.get_bytes().to_vec()
+}
+
+#[test]
+fn test_option_encoding() {
+    let context = Context::new().with_option_encoding(OptionEncoding::Undefined);
+    let value: Vec<Option<u8>> = vec![None, Some(1)];
+    let bytes = to_bytes(&value, &context);
+    assert_eq!(bytes, b"\x82\xf7\x01");
+    assert_eq!(from_bytes::<Vec<Option<u8>>>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_canonical() {
+    let context = Context::new().with_canonical(true);
+    let map: HashMap<&str, f64> = vec![("bb", 1.0), ("a", 1.5), ("c", 0.5)]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        to_bytes(&map, &context),
+        b"\xa3\x61a\xf9\x3e\x00\x61c\xf9\x38\x00\x62bb\xf9\x3c\x00"
+    );
+    assert_eq!(to_bytes(&1.5f64, &context), b"\xf9\x3e\x00");
+}
+
+#[test]
+fn test_extensions() {
+    #[derive(Debug, PartialEq)]
+    struct Version(u8);
+
+    let mut context = Context::new();
+    assert_eq!(context.insert(Version(1)), None);
+    assert_eq!(context.insert(Version(2)), Some(Version(1)));
+    context.get_mut::<Version>().unwrap().0 += 1;
+    assert_eq!(context.get::<Version>(), Some(&Version(3)));
+    assert_eq!(context.remove::<Version>(), Some(Version(3)));
+    assert_eq!(context.get::<Version>(), None);
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    assert_send_sync(&context);
+}
+
+#[cfg(feature = "iana_std")]
+#[test]
+fn test_typed_arrays() {
+    use cbor_enhanced::Endianness;
+
+    let values: Vec<u16> = vec![1, 0x0203];
+    let context = Context::new().with_typed_arrays(Some(Endianness::Big));
+    let bytes = to_bytes(&values, &context);
+    assert_eq!(bytes, b"\xd8\x41\x44\x00\x01\x02\x03");
+    assert_eq!(from_bytes::<Vec<u16>>(&bytes).unwrap(), values);
+
+    let context = Context::new().with_typed_arrays(Some(Endianness::Little));
+    let bytes = to_bytes(&values, &context);
+    assert_eq!(bytes, b"\xd8\x45\x44\x01\x00\x03\x02");
+    assert_eq!(from_bytes::<Vec<u16>>(&bytes).unwrap(), values);
+
+    assert_eq!(to_bytes(&values, &Context::new()), b"\x82\x01\x19\x02\x03");
+
+    let values: Vec<i16> = vec![-1, 2];
+    let context = Context::new().with_typed_arrays(Some(Endianness::Little));
+    let bytes = to_bytes(&values, &context);
+    assert_eq!(from_bytes::<Vec<i16>>(&bytes).unwrap(), values);
+
+    // other tags are skipped like for any other vector
+    let values = from_bytes::<Vec<u16>>(b"\xd9\xd9\xf7\x82\x01\x02").unwrap();
+    assert_eq!(values, vec![1, 2]);
+}
+
+#[cfg(feature = "iana_chrono")]
+#[test]
+fn test_datetime() {
+    use cbor_enhanced::{DateTimeFormat, Precision};
+    use chrono::{DateTime, FixedOffset};
+
+    let datetime = DateTime::parse_from_rfc3339("2013-03-21T20:04:00Z").unwrap();
+    let context = Context::new().with_datetime_precision(Precision::Seconds);
+    let bytes = to_bytes(&datetime, &context);
+    assert_eq!(bytes, b"\xc1\x1a\x51\x4b\x67\xb0");
+    assert_eq!(
+        from_bytes::<DateTime<FixedOffset>>(&bytes).unwrap(),
+        datetime
+    );
+
+    let context = Context::new().with_datetime_format(DateTimeFormat::Rfc3339);
+    let bytes = to_bytes(&datetime, &context);
+    assert_eq!(bytes[0], 0xc0);
+    assert_eq!(
+        from_bytes::<DateTime<FixedOffset>>(&bytes).unwrap(),
+        datetime
+    );
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use cbor_enhanced::{
+    canonicalize, CborError, Context, Deserializer, FloatEncoding, Serialize, Serializer, Value,
+};
+
+fn to_bytes<T: Serialize>(value: &T, context: &Context) -> Vec<u8> {
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer, context);
+    serializer.get_bytes().to_vec()
+}
+
+fn deterministic<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut serializer = Serializer::deterministic();
+    value.serialize(&mut serializer, &Context::new());
+    serializer.get_bytes().to_vec()
+}
+
+#[test]
+fn test_sorted_map_keys() {
+    let mut map = HashMap::new();
+    for key in &["aa", "b", "c", "a", "bb"] {
+        map.insert(key.to_string(), 1u64);
+    }
+    assert_eq!(
+        deterministic(&map),
+        b"\xa5\x61\x61\x01\x61\x62\x01\x61\x63\x01\x62\x61\x61\x01\x62\x62\x62\x01"
+    );
+
+    let mut map = HashMap::new();
+    map.insert(-1i64, 1u64);
+    map.insert(1000, 2);
+    map.insert(10, 3);
+    assert_eq!(deterministic(&map), b"\xa3\x0a\x03\x19\x03\xe8\x02\x20\x01");
+}
+
+#[test]
+fn test_shortest_floats() {
+    let cases: &[(f64, &[u8])] = &[
+        (0.0, b"\xf9\x00\x00"),
+        (-0.0, b"\xf9\x80\x00"),
+        (1.5, b"\xf9\x3e\x00"),
+        (65504.0, b"\xf9\x7b\xff"),
+        (5.960464477539063e-8, b"\xf9\x00\x01"),
+        (0.00006103515625, b"\xf9\x04\x00"),
+        (100000.0, b"\xfa\x47\xc3\x50\x00"),
+        (3.4028234663852886e+38, b"\xfa\x7f\x7f\xff\xff"),
+        (1.1, b"\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a"),
+        (f64::INFINITY, b"\xf9\x7c\x00"),
+        (f64::NEG_INFINITY, b"\xf9\xfc\x00"),
+        (f64::NAN, b"\xf9\x7e\x00"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(&deterministic(value), expected, "{}", value);
+        let (decoded, _) = Deserializer::new().take_float(expected, false).unwrap();
+        assert!(decoded == *value || value.is_nan() && decoded.is_nan());
+    }
+    assert_eq!(deterministic(&1.5f32), b"\xf9\x3e\x00");
+}
+
+#[test]
+fn test_canonicalize() {
+    // indefinite map with unsorted keys, a non shortest head and a double precision float
+    let input = b"\xbf\x62\x62\x62\x18\x01\x61\x61\x9f\xfb\x3f\xf8\x00\x00\x00\x00\x00\x00\xff\xff";
+    let canonical = canonicalize(input).unwrap();
+    assert_eq!(canonical, b"\xa2\x61\x61\x81\xf9\x3e\x00\x62\x62\x62\x01");
+    assert_eq!(canonicalize(&canonical).unwrap(), canonical);
+    assert!(canonicalize(b"\x01\x02").is_err());
+    assert!(matches!(
+        canonicalize(b"\xff"),
+        Err(CborError::UnexpectedBreak)
+    ));
+    assert!(matches!(
+        canonicalize(b"\xa2\x01\x01\x01\x02"),
+        Err(CborError::DuplicateMapKey(key)) if key == b"\x01"
+    ));
+    // the same key in a different encoding is a duplicate too
+    assert!(canonicalize(b"\xa2\x01\x01\x18\x01\x02").is_err());
+
+    let value = Value::from_diagnostic(r#"{"b": 1, 10: 2, "a": 3}"#).unwrap();
+    let mut serializer = Serializer::deterministic();
+    serializer.write_value(&value);
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\xa3\x0a\x02\x61\x61\x03\x61\x62\x01"
+    );
+}
+
+#[test]
+fn test_preferred_floats() {
+    let mut serializer = Serializer::new();
+    serializer.write_float_preferred(1.5);
+    serializer.write_float_preferred(f64::NEG_INFINITY);
+    serializer.write_float_preferred(-f64::NAN);
+    serializer.write_f64(1.5);
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\xf9\x3e\x00\xf9\xfc\x00\xf9\x7e\x00\xfb\x3f\xf8\x00\x00\x00\x00\x00\x00"
+    );
+
+    let mut context = Context::new();
+    let values = vec![0.5f64, 100000.0, 1.1];
+    assert_eq!(to_bytes(&values, &context)[1], 0xfb);
+    context.float_encoding = FloatEncoding::Preferred;
+    assert_eq!(
+        to_bytes(&values, &context),
+        b"\x83\xf9\x38\x00\xfa\x47\xc3\x50\x00\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a"
+    );
+    assert_eq!(to_bytes(&65504f32, &context), b"\xf9\x7b\xff");
+    context.float_encoding = FloatEncoding::Declared;
+    assert_eq!(to_bytes(&65504f32, &context), b"\xfa\x47\x7f\xe0\x00");
+}
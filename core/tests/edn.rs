@@ -0,0 +1,120 @@
+use cbor_enhanced::{cbor, CborError, Deserializer, Serializer, Value};
+
+fn encode(diagnostic: &str) -> Vec<u8> {
+    let mut serializer = Serializer::new();
+    serializer.write_value(&Value::from_diagnostic(diagnostic).unwrap());
+    serializer.get_bytes().to_vec()
+}
+
+fn assert_invalid(diagnostic: &str, expected_offset: usize) {
+    match Value::from_diagnostic(diagnostic) {
+        Err(CborError::InvalidDiagnostic { offset, .. }) => {
+            assert_eq!(offset, expected_offset, "{}", diagnostic)
+        }
+        other => panic!(
+            "expected invalid diagnostic for {} but got {:?}",
+            diagnostic, other
+        ),
+    }
+}
+
+#[test]
+fn test_from_diagnostic() {
+    let value = Value::from_diagnostic(
+        r#"{
+            "name": "cbor", / block comment /
+            1: [1, -2, 1.5, null, [true, undefined]], # line comment
+            h'0102': 0x10,
+            "tag": 1(1363896240)
+        }"#,
+    )
+    .unwrap();
+    let expected = cbor!({
+        "name" => "cbor",
+        1 => [1, -2, 1.5, null, [true, undefined]],
+        b"\x01\x02".as_ref() => 16,
+        "tag" => tag(1, 1363896240)
+    });
+    assert_eq!(value, expected);
+    assert_eq!(Value::from_diagnostic(&value.to_string()).unwrap(), value);
+}
+
+#[test]
+fn test_from_diagnostic_literals() {
+    assert_eq!(Value::from_diagnostic("-0").unwrap(), Value::U64(0));
+    let min = Value::from_diagnostic("-18446744073709551616").unwrap();
+    assert_eq!(min, Value::I128(-18446744073709551616));
+    assert_eq!(Value::from_diagnostic(&min.to_string()).unwrap(), min);
+    assert_eq!(
+        encode("-18446744073709551616"),
+        b"\x3b\xff\xff\xff\xff\xff\xff\xff\xff"
+    );
+    assert_invalid("-18446744073709551617", 1);
+    assert_invalid("18446744073709551616", 0);
+    assert_eq!(
+        Value::from_diagnostic("-18446744073709551615").unwrap(),
+        Value::I128(-18446744073709551615)
+    );
+    assert_eq!(Value::from_diagnostic("0b101_0").unwrap(), Value::U64(5));
+    assert_eq!(
+        Value::from_diagnostic("1.0e+300").unwrap(),
+        Value::F64(1.0e300)
+    );
+    assert_eq!(
+        Value::from_diagnostic("-Infinity").unwrap(),
+        Value::F64(f64::NEG_INFINITY)
+    );
+    assert_eq!(
+        Value::from_diagnostic("simple(255)").unwrap(),
+        Value::Simple(255)
+    );
+    assert_eq!(
+        Value::from_diagnostic(r#""\"\\𝄞""#).unwrap(),
+        Value::from("\"\\\u{1d11e}")
+    );
+    assert_eq!(
+        Value::from_diagnostic("b64'AQID' 'a'").ok(),
+        None,
+        "trailing input is rejected"
+    );
+    assert_eq!(
+        Value::from_diagnostic("[b64'AQID', b64'-_8', 'a']").unwrap(),
+        cbor!([
+            b"\x01\x02\x03".as_ref(),
+            b"\xfb\xff".as_ref(),
+            b"a".as_ref()
+        ])
+    );
+}
+
+#[test]
+fn test_from_diagnostic_indefinite() {
+    assert_eq!(
+        encode(r#"{_ "a": 1, "b": [_ 2, 3]}"#),
+        b"\xa2\x61\x61\x01\x61\x62\x82\x02\x03"
+    );
+    assert_eq!(
+        Value::from_diagnostic(r#"(_ "strea", "ming")"#).unwrap(),
+        Value::from("streaming")
+    );
+    assert_eq!(
+        encode("(_ h'01 02', h'030405')"),
+        b"\x45\x01\x02\x03\x04\x05"
+    );
+    let bytes = encode("[1, [2, 3], {4: 5}]");
+    let (value, _) = Deserializer::new().take_value(&bytes).unwrap();
+    assert_eq!(value.to_string(), "[1, [2, 3], {4: 5}]");
+}
+
+#[test]
+fn test_from_diagnostic_errors() {
+    assert_invalid("", 0);
+    assert_invalid("[1, 2", 5);
+    assert_invalid("{1 2}", 3);
+    assert_invalid("h'123'", 0);
+    assert_invalid("\"open", 5);
+    assert_invalid("/ open", 0);
+    assert_invalid("simple(24)", 10);
+    assert_invalid("(_ h'01', \"a\")", 0);
+    assert_invalid("1 2", 2);
+}
@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use cbor_enhanced::{CborError, Deserializer, Event, Parser, Serializer, Value};
+
+#[test]
+fn test_indefinite_collections() {
+    let mut serializer = Serializer::new();
+    serializer.begin_map_indefinite();
+    serializer.write_text("a");
+    serializer.begin_array_indefinite();
+    serializer.write_u64(1);
+    serializer.write_u64(2);
+    serializer.write_break();
+    serializer.write_break();
+    assert_eq!(serializer.get_bytes(), b"\xbf\x61\x61\x9f\x01\x02\xff\xff");
+    let (value, _) = Deserializer::new()
+        .take_value(serializer.get_bytes())
+        .unwrap();
+    assert_eq!(value["a"][1], Value::U64(2));
+
+    let mut serializer = Serializer::deterministic();
+    serializer.begin_array_indefinite();
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::IndefiniteNotAllowed)
+    ));
+}
+
+#[test]
+fn test_chunked_strings() {
+    let mut serializer = Serializer::new();
+    let mut chunks = serializer.begin_text_chunked();
+    chunks.write_chunk("strea");
+    chunks.write_chunk("ming");
+    chunks.finish();
+    let mut chunks = serializer.begin_bytes_chunked();
+    chunks.write_chunk(b"\x01\x02");
+    drop(chunks);
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\x7f\x65strea\x64ming\xff\x5f\x42\x01\x02\xff"
+    );
+    let events: Vec<Event<'_>> = Parser::new(serializer.get_bytes())
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(events[0], Event::StartText);
+    assert_eq!(events[1], Event::Text("strea"));
+
+    let mut serializer = Serializer::deterministic();
+    let mut chunks = serializer.begin_text_chunked();
+    chunks.write_chunk("strea");
+    chunks.write_chunk("ming");
+    chunks.finish();
+    assert_eq!(serializer.get_bytes(), b"\x69streaming");
+}
+
+#[test]
+fn test_bytes_from_reader() {
+    let data: Vec<u8> = (0..10).collect();
+    let mut serializer = Serializer::new();
+    let copied = serializer
+        .write_bytes_from_reader(Cursor::new(&data), 4)
+        .unwrap();
+    assert_eq!(copied, 10);
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\x5f\x44\x00\x01\x02\x03\x44\x04\x05\x06\x07\x42\x08\x09\xff"
+    );
+    let (bytes, _) = Deserializer::new()
+        .take_bytes_cow(serializer.get_bytes(), false)
+        .unwrap();
+    assert_eq!(bytes.as_ref(), data.as_slice());
+
+    let mut serializer = Serializer::new();
+    serializer
+        .write_bytes_from_reader(Cursor::new(Vec::new()), 4)
+        .unwrap();
+    assert_eq!(serializer.get_bytes(), b"\x5f\xff");
+}
@@ -0,0 +1,63 @@
+use cbor_enhanced::{CborError, Context, Deserializer};
+
+#[test]
+fn test_array_iter() {
+    let context = Context::new();
+    let mut deserializer = Deserializer::new();
+    let mut iter = deserializer
+        .take_array_iter::<u64>(b"\x83\x01\x02\x18\x2a\xf6", &context)
+        .unwrap();
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.next().unwrap().unwrap(), 1);
+    assert!(iter.remaining().is_none());
+    let rest: Vec<u64> = iter.by_ref().map(Result::unwrap).collect();
+    assert_eq!(rest, vec![2, 42]);
+    assert_eq!(iter.remaining(), Some(b"\xf6".as_ref()));
+    drop(iter);
+
+    let mut iter = deserializer
+        .take_array_iter::<&str>(b"\x9f\x61\x61\x61\x62\xff\x01", &context)
+        .unwrap();
+    assert_eq!(iter.size_hint(), (0, None));
+    let strings: Vec<&str> = iter.by_ref().map(Result::unwrap).collect();
+    assert_eq!(strings, vec!["a", "b"]);
+    assert_eq!(iter.remaining(), Some(b"\x01".as_ref()));
+}
+
+#[test]
+fn test_array_iter_error() {
+    let context = Context::new();
+    let mut deserializer = Deserializer::new();
+    let mut iter = deserializer
+        .take_array_iter::<u64>(b"\x83\x01\x61\x61\x03", &context)
+        .unwrap();
+    assert!(iter.next().unwrap().is_ok());
+    let error = iter.next().unwrap().unwrap_err();
+    assert_eq!(error.offset(), Some(2));
+    assert!(matches!(error.inner(), CborError::ExpectUnsigned(_)));
+    assert!(iter.next().is_none());
+    assert!(iter.remaining().is_none());
+}
+
+#[test]
+fn test_map_iter() {
+    let context = Context::new();
+    let mut deserializer = Deserializer::new();
+    let bytes = b"\xbf\x61\x61\x01\x61\x62\x02\xff";
+    let entries: Vec<(&str, u8)> = deserializer
+        .take_map_iter(bytes, &context)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(entries, vec![("a", 1), ("b", 2)]);
+
+    let bytes = b"\xa2\x61\x61\x01\x61\x61\x02";
+    deserializer.set_strict(true);
+    let result: Result<Vec<(&str, u8)>, _> = deserializer
+        .take_map_iter(bytes, &context)
+        .unwrap()
+        .collect();
+    let error = result.unwrap_err();
+    assert!(matches!(error.inner(), CborError::DuplicateMapKey(_)));
+    assert_eq!(error.offset(), Some(4));
+}
@@ -0,0 +1,92 @@
+use cbor_enhanced::{cbor, CborError, JsonPolicy, Value};
+use serde_json::json;
+
+fn strict_error(value: &Value) -> &'static str {
+    match value.to_json(JsonPolicy::Strict) {
+        Err(CborError::NotJsonCompatible(reason)) => reason,
+        other => panic!("expected strict conversion to fail but got {:?}", other),
+    }
+}
+
+#[test]
+fn test_to_json() {
+    let value = cbor!({
+        "a" => [1, -2, 1.5, true, null],
+        "bytes" => b"\xfb\xff\x01".as_ref(),
+        "text" => "hello"
+    });
+    let expected = json!({
+        "a": [1, -2, 1.5, true, null],
+        "bytes": "-_8B",
+        "text": "hello"
+    });
+    assert_eq!(value.to_json(JsonPolicy::Strict).unwrap(), expected);
+    assert_eq!(
+        Value::from_json(&expected)
+            .to_json(JsonPolicy::Lossy)
+            .unwrap()["bytes"],
+        "-_8B"
+    );
+}
+
+#[test]
+fn test_to_json_tags() {
+    let value = cbor!([
+        tag(2, b"\x01\x00".as_ref()),
+        tag(3, b"\x01\x00".as_ref()),
+        tag(22, [b"\xfb\xff".as_ref(), tag(23, b"\x01\xab".as_ref())]),
+        tag(21, b"\xfb\xff".as_ref())
+    ]);
+    assert_eq!(
+        value.to_json(JsonPolicy::Strict).unwrap(),
+        json!(["AQA", "~AQA", ["+/8=", "01ab"], "-_8"])
+    );
+    let value = cbor!(tag(1, 1363896240));
+    assert_eq!(value.to_json(JsonPolicy::Lossy).unwrap(), json!(1363896240));
+    assert_eq!(strict_error(&value), "tag");
+}
+
+#[test]
+fn test_to_json_lossy() {
+    let value = cbor!({
+        1 => f64::NAN,
+        b"\x01".as_ref() => f64::INFINITY,
+        "u" => undefined,
+        "s" => Value::Simple(16),
+        "big" => Value::I128(-18446744073709551616)
+    });
+    assert_eq!(
+        value.to_json(JsonPolicy::Lossy).unwrap(),
+        json!({
+            "1": null,
+            "AQ": null,
+            "u": null,
+            "s": null,
+            "big": -18446744073709551616.0
+        })
+    );
+    assert_eq!(strict_error(&cbor!(f64::NAN)), "NaN or Infinity");
+    assert_eq!(strict_error(&cbor!({1 => 2})), "non text map key");
+    assert_eq!(strict_error(&cbor!(undefined)), "undefined");
+    assert_eq!(strict_error(&Value::Simple(16)), "simple value");
+    assert_eq!(
+        strict_error(&cbor!({"a" => 1, "a" => 2})),
+        "duplicate map key"
+    );
+}
+
+#[test]
+fn test_from_json() {
+    let value = Value::from_json(&json!({
+        "a": [1, -2, 1.5, 18446744073709551615u64],
+        "b": null,
+        "c": "text"
+    }));
+    assert_eq!(value["a"][0], Value::U64(1));
+    assert_eq!(value["a"][1], Value::I128(-2));
+    assert_eq!(value["a"][2], Value::F64(1.5));
+    assert_eq!(value["a"][3], Value::U64(u64::MAX));
+    assert!(value["b"].is_null());
+    assert_eq!(value["c"].as_text(), Some("text"));
+    assert_eq!(Value::from(json!(false)), Value::Bool(false));
+}
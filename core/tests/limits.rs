@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use cbor_enhanced::{CborError, Context, DecodeLimits, Deserialize, Deserializer};
+
+fn assert_limit<T>(result: Result<T, CborError>, expected: &str) {
+    match result.as_ref().map_err(CborError::inner) {
+        Err(CborError::LimitExceeded { limit, .. }) => assert_eq!(*limit, expected),
+        Err(e) => panic!("expected limit {} but got {:?}", expected, e),
+        Ok(_) => panic!("expected limit {} to be exceeded", expected),
+    }
+}
+
+#[test]
+fn test_max_depth() {
+    let mut bytes = vec![0x81u8; 200];
+    bytes.push(0x01);
+    let deserializer = Deserializer::new();
+    assert_limit(deserializer.take_value(&bytes), "max_depth");
+
+    let deserializer = Deserializer::with_limits(DecodeLimits::default().with_max_depth(300));
+    assert!(deserializer.take_value(&bytes).is_ok());
+
+    let mut deserializer = Deserializer::new();
+    let result =
+        Vec::<Vec<Vec<u64>>>::deserialize(&mut deserializer, b"\x81\x81\x81\x01", &Context::new());
+    assert!(result.is_ok());
+    deserializer.set_limits(DecodeLimits::default().with_max_depth(2));
+    let result =
+        Vec::<Vec<Vec<u64>>>::deserialize(&mut deserializer, b"\x81\x81\x81\x01", &Context::new());
+    assert_limit(result, "max_depth");
+}
+
+#[test]
+fn test_claimed_length_does_not_allocate() {
+    let bytes = b"\x9b\xff\xff\xff\xff\xff\xff\xff\xff";
+    let mut deserializer = Deserializer::new();
+    let result = Vec::<u64>::deserialize(&mut deserializer, bytes, &Context::new());
+    assert!(result.is_err());
+    let result = HashMap::<u64, u64>::deserialize(
+        &mut deserializer,
+        b"\xbb\xff\xff\xff\xff\xff\xff\xff\xff",
+        &Context::new(),
+    );
+    assert!(result.is_err());
+    assert!(deserializer.take_value(bytes).is_err());
+}
+
+#[test]
+fn test_max_collection_length() {
+    let limits = DecodeLimits::default().with_max_collection_length(2);
+    let mut deserializer = Deserializer::with_limits(limits);
+    let result = Vec::<u64>::deserialize(&mut deserializer, b"\x83\x01\x02\x03", &Context::new());
+    assert_limit(result, "max_collection_length");
+    let result =
+        Vec::<u64>::deserialize(&mut deserializer, b"\x9f\x01\x02\x03\xff", &Context::new());
+    assert_limit(result, "max_collection_length");
+    assert_limit(
+        deserializer.take_value(b"\xa3\x01\x02\x03\x04\x05\x06"),
+        "max_collection_length",
+    );
+    assert!(deserializer.take_value(b"\x82\x01\x02").is_ok());
+}
+
+#[test]
+fn test_max_string_length_and_allocation() {
+    let limits = DecodeLimits::default().with_max_string_length(3);
+    let deserializer = Deserializer::with_limits(limits);
+    assert_limit(
+        deserializer.take_text(b"\x64\x49\x45\x54\x46", true),
+        "max_string_length",
+    );
+    assert_limit(
+        deserializer.take_bytes_cow(b"\x5f\x42\x01\x02\x42\x03\x04\xff", true),
+        "max_string_length",
+    );
+
+    let limits = DecodeLimits::default().with_max_allocated_bytes(3);
+    let mut deserializer = Deserializer::with_limits(limits);
+    let result = String::deserialize(&mut deserializer, b"\x64\x49\x45\x54\x46", &Context::new());
+    assert_limit(result, "max_allocated_bytes");
+    deserializer.reset();
+    assert!(deserializer
+        .take_text(b"\x64\x49\x45\x54\x46", true)
+        .is_ok());
+}
+
+#[test]
+fn test_allocation_per_top_level_item() {
+    let limits = DecodeLimits::default().with_max_allocated_bytes(32);
+    let mut deserializer = Deserializer::with_limits(limits);
+    for _ in 0..3 {
+        let result = Vec::<u8>::deserialize(&mut deserializer, b"\x45hello", &Context::new());
+        assert_eq!(result.unwrap().0, b"hello");
+        let result =
+            Vec::<Vec<u8>>::deserialize(&mut deserializer, b"\x81\x43abc", &Context::new());
+        assert!(result.is_ok());
+        let result = deserializer.decode::<String>(b"\x65hello", &Context::new());
+        assert!(result.is_ok());
+    }
+    let result =
+        Vec::<Vec<u8>>::deserialize(&mut deserializer, b"\x81\x4a0123456789", &Context::new());
+    assert_limit(result, "max_allocated_bytes");
+}
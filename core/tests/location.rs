@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use cbor_enhanced::{
+    from_bytes, CborError, Context, DecodePath, Deserialize, Deserializer, PathSegment,
+};
+
+#[test]
+fn test_nested_array_location() {
+    let deserializer = Deserializer::new();
+    let error = deserializer
+        .take_value(b"\x82\x01\x82\x02\x62\x61")
+        .unwrap_err();
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(
+        error.path(),
+        Some(&DecodePath(vec![
+            PathSegment::Index(1),
+            PathSegment::Index(1)
+        ]))
+    );
+    assert!(matches!(error.inner(), CborError::Incomplete(_)));
+    assert!(error.to_string().ends_with("at offset 4 ($[1][1])"));
+}
+
+#[test]
+fn test_map_key_location() {
+    let deserializer = Deserializer::new();
+    let error = deserializer
+        .take_value(b"\xa1\x61\x61\x81\x1c")
+        .unwrap_err();
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(error.path().unwrap().to_string(), "$[\"a\"][0]");
+
+    let mut deserializer = Deserializer::new();
+    let error = Vec::<HashMap<String, u64>>::deserialize(
+        &mut deserializer,
+        b"\x81\xa1\x61\x61\x61\x62",
+        &Context::new(),
+    )
+    .unwrap_err();
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(error.path().unwrap().to_string(), "$[0][\"a\"]");
+    assert!(matches!(error.inner(), CborError::ExpectUnsigned(_)));
+}
+
+#[test]
+fn test_top_level_error_location() {
+    let deserializer = Deserializer::new();
+    let error = deserializer.take_value(b"\x62\x61").unwrap_err();
+    assert_eq!(error.offset(), Some(0));
+    assert_eq!(error.path(), Some(&DecodePath::default()));
+    assert!(matches!(error.inner(), CborError::Incomplete(_)));
+
+    let error = from_bytes::<u16>(b"\x61a").unwrap_err();
+    assert_eq!(error.offset(), Some(0));
+    assert!(matches!(error.inner(), CborError::ExpectUnsigned(_)));
+}
+
+#[test]
+fn test_offset_from_input_start() {
+    // the array is nested in a tag, offsets still count from the tag
+    let error = from_bytes::<Vec<u8>>(b"\xd9\xd9\xf7\x82\x01\x61a").unwrap_err();
+    assert_eq!(error.offset(), Some(5));
+    assert_eq!(error.path().unwrap().to_string(), "$[1]");
+}
@@ -0,0 +1,66 @@
+use std::borrow::Cow;
+
+use cbor_enhanced::{
+    from_bytes, to_vec, CborError, Context, DecodeLimits, Deserialize, Deserializer, OwnedValue,
+    Serializer, Value,
+};
+
+#[test]
+fn test_into_owned() {
+    let expected = b"\xa1\x61\x61\x82\x43\x01\x02\x03\xc1\x18\x2a";
+    let bytes = expected.to_vec();
+    let owned: Value<'static> = Deserializer::new()
+        .take_value(&bytes)
+        .unwrap()
+        .0
+        .into_owned();
+    drop(bytes);
+    match &owned {
+        Value::Map(entries) => assert_eq!(entries[0].0, Value::Text("a".into())),
+        _ => panic!("expected map"),
+    }
+
+    let handle = std::thread::spawn(move || {
+        let mut serializer = Serializer::new();
+        serializer.write_value(&owned);
+        serializer.get_bytes().to_vec()
+    });
+    assert_eq!(handle.join().unwrap(), expected);
+}
+
+#[test]
+fn test_value_traits() {
+    let bytes = b"\x82\xa1\x61\x61\x43\x01\x02\x03\x20".to_vec();
+    let values: Vec<Value> = from_bytes(&bytes).unwrap();
+    assert!(matches!(&values[0]["a"], Value::Bytes(Cow::Borrowed(_))));
+    assert_eq!(values[0]["a"], Value::Bytes(vec![1, 2, 3].into()));
+    assert_eq!(values[1], Value::I128(-1));
+    assert_eq!(to_vec(&values), bytes);
+
+    let limits = DecodeLimits::default().with_max_allocated_bytes(4);
+    let mut deserializer = Deserializer::with_limits(limits);
+    let result = Value::deserialize(&mut deserializer, b"\x45hello", &Context::new());
+    assert!(result.is_ok());
+    let result = deserializer.take_owned_value(b"\x45hello");
+    assert!(matches!(
+        result.unwrap_err().inner(),
+        CborError::LimitExceeded {
+            limit: "max_allocated_bytes",
+            ..
+        }
+    ));
+    let (owned, _) = deserializer.take_owned_value(b"\x44hell").unwrap();
+    assert_eq!(*owned, Value::Bytes(b"hell".to_vec().into()));
+}
+
+#[test]
+fn test_owned_value_from_any_buffer() {
+    let bytes = b"\x82\xa1\x61\x61\x43\x01\x02\x03\x20".to_vec();
+    let values: Vec<OwnedValue> = from_bytes(&bytes).unwrap();
+    let option: Option<OwnedValue> = from_bytes(&bytes[1..]).unwrap();
+    drop(bytes);
+    assert_eq!(values[0]["a"], Value::Bytes(vec![1, 2, 3].into()));
+    assert_eq!(*values[1], Value::I128(-1));
+    assert_eq!(option.unwrap(), values[0]);
+    assert_eq!(to_vec(&values), b"\x82\xa1\x61\x61\x43\x01\x02\x03\x20");
+}
@@ -0,0 +1,72 @@
+use cbor_enhanced::{CborError, DecodeLimits, Event, IanaTag, Parser};
+
+fn events(bytes: &[u8]) -> Vec<Event<'_>> {
+    Parser::new(bytes).map(Result::unwrap).collect()
+}
+
+#[test]
+fn test_parser_events() {
+    let bytes = b"\xa2\x61\x61\x82\x01\x20\x62\x62\x63\xc1\xf9\x3c\x00\x00";
+    assert_eq!(
+        events(bytes),
+        vec![
+            Event::StartMap(Some(2)),
+            Event::Text("a"),
+            Event::StartArray(Some(2)),
+            Event::Unsigned(1),
+            Event::Negative(-1),
+            Event::Text("bc"),
+            Event::Tag(IanaTag::EpochBasedTime),
+            Event::Float(1.0),
+            Event::Unsigned(0),
+        ]
+    );
+}
+
+#[test]
+fn test_parser_indefinite_and_depth() {
+    let bytes = b"\x9f\x5f\x41\x01\x42\x02\x03\xff\x80\xf4\xf8\xff\xff";
+    let mut parser = Parser::new(bytes);
+    let mut depths = Vec::new();
+    let mut found = Vec::new();
+    while let Some(event) = parser.next_event().unwrap() {
+        found.push(event);
+        depths.push(parser.depth());
+    }
+    assert_eq!(
+        found,
+        vec![
+            Event::StartArray(None),
+            Event::StartBytes,
+            Event::Bytes(b"\x01"),
+            Event::Bytes(b"\x02\x03"),
+            Event::Break,
+            Event::StartArray(Some(0)),
+            Event::Bool(false),
+            Event::Simple(255),
+            Event::Break,
+        ]
+    );
+    assert_eq!(depths, vec![1, 2, 2, 2, 1, 1, 1, 1, 0]);
+    assert_eq!(parser.offset(), bytes.len());
+}
+
+#[test]
+fn test_parser_errors() {
+    let result: Result<Vec<Event>, _> = Parser::new(b"\x82\x01").collect();
+    assert!(matches!(result, Err(CborError::Incomplete(_))));
+    let result: Result<Vec<Event>, _> = Parser::new(b"\x01\xff").collect();
+    assert!(matches!(result, Err(CborError::UnexpectedBreak)));
+    let result: Result<Vec<Event>, _> = Parser::new(b"\x5f\x61\x61\xff").collect();
+    assert!(matches!(result, Err(CborError::InvalidChunk(_))));
+    let result: Result<Vec<Event>, _> = Parser::new(b"\xc1").collect();
+    assert!(matches!(result, Err(CborError::Incomplete(_))));
+    let result: Result<Vec<Event>, _> = Parser::new(b"\x9f\xc1\xff").collect();
+    assert!(matches!(result, Err(CborError::UnexpectedBreak)));
+    let result: Result<Vec<Event>, _> = Parser::new(b"\xc1\xc1\x01").collect();
+    assert_eq!(result.unwrap().len(), 3);
+    let result: Result<Vec<Event>, _> = Parser::new(b"\x81\x81\x81\x01")
+        .with_limits(DecodeLimits::default().with_max_depth(2))
+        .collect();
+    assert!(matches!(result, Err(CborError::LimitExceeded { .. })));
+}
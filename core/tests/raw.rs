@@ -0,0 +1,62 @@
+use cbor_enhanced::{CborError, Context, Deserialize, Deserializer, RawValue, Serializer, Value};
+
+#[test]
+fn test_skip_value() {
+    let deserializer = Deserializer::new();
+    for bytes in &[
+        b"\x1b\x00\x00\x00\x01\x00\x00\x00\x00".as_ref(),
+        b"\xc1\xfb\x41\xd4\x52\xd9\xec\x20\x00\x00",
+        b"\x83\x01\x82\x02\x03\x82\x04\x05",
+        b"\xbf\x61\x61\x01\x61\x62\x9f\x02\x03\xff\xff",
+        b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff",
+        b"\xf8\xff",
+        b"\x5f\x41\x01\x40\xff",
+    ] {
+        let mut with_trailing = bytes.to_vec();
+        with_trailing.push(0x01);
+        assert_eq!(deserializer.skip_value(&with_trailing).unwrap(), b"\x01");
+    }
+    assert!(deserializer.skip_value(b"\x83\x01\x02").is_err());
+    assert!(deserializer.skip_value(b"\x9f\x01\x02").is_err());
+    for bytes in &[
+        b"\x5f\x01\xff".as_ref(),
+        b"\x7f\x41a\xff",
+        b"\x5f\x5f\xff\xff",
+    ] {
+        assert!(matches!(
+            deserializer.skip_value(bytes),
+            Err(CborError::InvalidChunk(_))
+        ));
+        assert!(RawValue::from_bytes(bytes).is_err());
+    }
+}
+
+#[test]
+fn test_raw_value() {
+    let bytes = b"\x83\x01\xa1\x61\x61\x82\x02\x03\x63\x61\x62\x63";
+    let mut deserializer = Deserializer::new();
+    let (items, _) =
+        Vec::<RawValue>::deserialize(&mut deserializer, bytes, &Context::new()).unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1].as_bytes(), b"\xa1\x61\x61\x82\x02\x03");
+    assert_eq!(
+        items[2]
+            .deserialize_into::<&str>(&mut deserializer, &Context::new())
+            .unwrap(),
+        "abc"
+    );
+    assert_eq!(items[0].to_value().unwrap(), Value::U64(1));
+
+    let mut serializer = Serializer::new();
+    serializer.write_array_def(3);
+    items
+        .iter()
+        .for_each(|item| serializer.write_raw(item.as_bytes()));
+    assert_eq!(serializer.get_bytes(), bytes.as_ref());
+
+    assert!(RawValue::from_bytes(b"\x82\x01\x02").is_ok());
+    assert!(matches!(
+        RawValue::from_bytes(b"\x01\x02"),
+        Err(CborError::TrailingBytes(1))
+    ));
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+
+use cbor_enhanced::{
+    from_bytes_serde, to_vec_serde, CborError, DecodeLimits, Deserializer, OwnedValue,
+    SerdeDeserializer, Serializer, Value,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u8,
+    nick: Option<String>,
+    tags: Vec<u16>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Line(i32, i32),
+    Rect { width: u32, height: u32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Borrowed<'a> {
+    name: &'a str,
+    #[serde(with = "bytes")]
+    data: &'a [u8],
+}
+
+mod bytes {
+    pub fn serialize<S: serde::Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(data)
+    }
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<&'de [u8], D::Error> {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+fn round_trip<T>(value: T, expected: &[u8])
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    let bytes = to_vec_serde(&value).unwrap();
+    assert_eq!(bytes, expected);
+    assert_eq!(from_bytes_serde::<T>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_struct() {
+    let person = Person {
+        name: "Tim".to_string(),
+        age: 42,
+        nick: None,
+        tags: vec![1, 1000],
+    };
+    round_trip(
+        person,
+        b"\xa4\x64name\x63Tim\x63age\x18\x2a\x64nick\xf6\x64tags\x82\x01\x19\x03\xe8",
+    );
+}
+
+#[test]
+fn test_enum() {
+    round_trip(Shape::Empty, b"\x65Empty");
+    round_trip(
+        Shape::Circle(1.5),
+        b"\xa1\x66Circle\xfb\x3f\xf8\x00\x00\x00\x00\x00\x00",
+    );
+    round_trip(Shape::Line(1, -1), b"\xa1\x64Line\x82\x01\x20");
+    round_trip(
+        Shape::Rect {
+            width: 1,
+            height: 2,
+        },
+        b"\xa1\x64Rect\xa2\x65width\x01\x66height\x02",
+    );
+}
+
+#[test]
+fn test_borrowed() {
+    let bytes = to_vec_serde(&Borrowed {
+        name: "abc",
+        data: &[1, 2, 3],
+    })
+    .unwrap();
+    let borrowed: Borrowed = from_bytes_serde(&bytes).unwrap();
+    assert_eq!(borrowed.name, "abc");
+    assert_eq!(borrowed.data, &[1, 2, 3]);
+    assert!(bytes.as_ptr_range().contains(&borrowed.name.as_ptr()));
+    assert!(bytes.as_ptr_range().contains(&borrowed.data.as_ptr()));
+}
+
+#[test]
+fn test_indefinite_input() {
+    let value: Vec<BTreeMap<String, u8>> = from_bytes_serde(b"\x9f\xbf\x61a\x01\xff\xff").unwrap();
+    assert_eq!(value[0]["a"], 1);
+    let value: Shape = from_bytes_serde(b"\xbf\x66Circle\xf9\x3c\x00\xff").unwrap();
+    assert_eq!(value, Shape::Circle(1.0));
+}
+
+#[test]
+fn test_deterministic_map() {
+    let mut serializer = Serializer::deterministic();
+    let map: HashMap<&str, u8> = vec![("bb", 1), ("a", 2), ("c", 3)].into_iter().collect();
+    map.serialize(&mut serializer).unwrap();
+    assert_eq!(
+        serializer.into_bytes().as_ref(),
+        b"\xa3\x61a\x02\x61c\x03\x62bb\x01"
+    );
+}
+
+#[test]
+fn test_big_integers() {
+    round_trip(
+        u64::MAX as u128 + 1,
+        b"\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00",
+    );
+    round_trip(-1i128, b"\x20");
+}
+
+#[test]
+fn test_value() {
+    let bytes = b"\xa2\x61a\x82\x01\x20\x61b\x43\x01\x02\x03";
+    let value: Value = from_bytes_serde(bytes).unwrap();
+    assert_eq!(value["a"][1], Value::I128(-1));
+    assert_eq!(to_vec_serde(&value).unwrap(), bytes);
+
+    let buffer = bytes.to_vec();
+    let owned: OwnedValue = from_bytes_serde(&buffer).unwrap();
+    drop(buffer);
+    assert_eq!(*owned, value);
+}
+
+#[test]
+fn test_errors() {
+    let result = from_bytes_serde::<Person>(b"\xa1\x63age\x19\x01\x00");
+    let error = result.unwrap_err();
+    assert!(matches!(error.inner(), CborError::Custom(_)));
+    assert!(from_bytes_serde::<(u8, u8)>(b"\x83\x01\x02\x03").is_err());
+}
+
+#[test]
+fn test_error_offset_after_tag() {
+    let error = from_bytes_serde::<(u8, u8)>(b"\xd9\xd9\xf7\x82\x01\x61a").unwrap_err();
+    assert_eq!(error.offset(), Some(5));
+    assert_eq!(error.path().unwrap().to_string(), "$<55799>[1]");
+}
+
+#[test]
+fn test_limits_and_strict_mode() {
+    let limits = DecodeLimits::default().with_max_collection_length(2);
+    for bytes in [&b"\x83\x01\x02\x03"[..], b"\x9f\x01\x02\x03\xff"].iter() {
+        let mut deserializer = Deserializer::with_limits(limits);
+        let result = Vec::<u8>::deserialize(&mut SerdeDeserializer::new(&mut deserializer, bytes));
+        assert!(matches!(
+            result.unwrap_err().inner(),
+            CborError::LimitExceeded {
+                limit: "max_collection_length",
+                ..
+            }
+        ));
+    }
+    let mut deserializer = Deserializer::with_limits(limits);
+    let result = BTreeMap::<u8, u8>::deserialize(&mut SerdeDeserializer::new(
+        &mut deserializer,
+        b"\xa3\x01\x01\x02\x02\x03\x03",
+    ));
+    assert!(result.is_err());
+
+    let bytes = b"\xa2\x61a\x01\x61a\x02";
+    let map: BTreeMap<String, u8> = from_bytes_serde(bytes).unwrap();
+    assert_eq!(map["a"], 2);
+    let mut deserializer = Deserializer::strict();
+    let result =
+        BTreeMap::<String, u8>::deserialize(&mut SerdeDeserializer::new(&mut deserializer, bytes));
+    assert!(matches!(
+        result.unwrap_err().inner(),
+        CborError::DuplicateMapKey(key) if key == b"\x61a"
+    ));
+}
+
+#[test]
+fn test_failed_compound_restores_output() {
+    use serde::ser::{Error, SerializeSeq};
+
+    struct Failing;
+    impl Serialize for Failing {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(None)?;
+            seq.serialize_element(&1u8)?;
+            seq.serialize_element(&Value::Simple(42))?;
+            Err(S::Error::custom("unreachable"))
+        }
+    }
+
+    let mut serializer = Serializer::new();
+    assert!(Failing.serialize(&mut serializer).is_err());
+    2u8.serialize(&mut serializer).unwrap();
+    assert!(serializer.error().is_none());
+    assert_eq!(serializer.into_bytes().as_ref(), b"\x02");
+}
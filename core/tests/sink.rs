@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Write};
+
+use cbor_enhanced::{to_slice, to_vec, to_writer, CborError, Context, Serialize, Serializer};
+
+struct FailingWriter;
+
+impl Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        Err(Error::new(ErrorKind::BrokenPipe, "closed"))
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn sample() -> HashMap<String, Vec<u64>> {
+    let mut map = HashMap::new();
+    map.insert("b".to_string(), vec![1, 1000]);
+    map.insert("a".to_string(), vec![]);
+    map
+}
+
+#[test]
+fn test_writer_and_buf_mut() {
+    let expected = to_vec(&sample());
+
+    let mut written = Vec::new();
+    assert_eq!(to_writer(&sample(), &mut written).unwrap(), expected.len());
+    assert_eq!(written.len(), expected.len());
+
+    let mut buffer = Vec::new();
+    let mut serializer = Serializer::from_buf_mut(&mut buffer);
+    serializer.write_u64(1000);
+    serializer.write_text("a");
+    assert!(serializer.get_bytes().is_empty());
+    assert_eq!(serializer.finish().unwrap(), 5);
+    assert_eq!(buffer, b"\x19\x03\xe8\x61\x61");
+
+    let mut serializer = Serializer::from_writer(FailingWriter);
+    serializer.write_u64(1);
+    serializer.write_u64(2);
+    assert!(serializer.error().is_some());
+    assert!(matches!(serializer.finish(), Err(CborError::IoError(_))));
+}
+
+#[test]
+fn test_slice() {
+    let mut slice = [0u8; 8];
+    assert_eq!(to_slice(&vec![1u64, 1000], &mut slice).unwrap(), 5);
+    assert_eq!(&slice[..5], b"\x82\x01\x19\x03\xe8");
+
+    let mut slice = [0u8; 4];
+    let mut serializer = Serializer::from_slice(&mut slice);
+    serializer.write_text("abc");
+    assert_eq!(serializer.get_bytes(), b"\x63abc");
+    serializer.write_u8(1);
+    assert_eq!(serializer.written(), 4);
+    assert!(matches!(
+        serializer.finish(),
+        Err(CborError::BufferOverflow(4))
+    ));
+
+    let mut slice = [0u8; 2];
+    let mut serializer = Serializer::from_buf_mut(&mut slice[..]);
+    serializer.write_u64(1000);
+    assert!(matches!(
+        serializer.finish(),
+        Err(CborError::BufferOverflow(2))
+    ));
+}
+
+#[test]
+fn test_deterministic_into_writer() {
+    let mut written = Vec::new();
+    let mut serializer = Serializer::from_writer(&mut written);
+    serializer.set_deterministic(true);
+    sample().serialize(&mut serializer, &Context::new());
+    serializer.finish().unwrap();
+    assert_eq!(written, b"\xa2\x61\x61\x80\x61\x62\x82\x01\x19\x03\xe8");
+}
+
+#[test]
+fn test_reserved_simple_value() {
+    let mut serializer = Serializer::new();
+    serializer.write_simple(24);
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::InvalidSimpleValue(24))
+    ));
+    serializer.write_simple(32);
+    assert!(serializer.get_bytes().is_empty());
+
+    let mut serializer = Serializer::new();
+    serializer.write_simple(16);
+    serializer.write_simple(255);
+    assert_eq!(serializer.get_bytes(), b"\xf0\xf8\xff");
+}
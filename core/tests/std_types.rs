@@ -0,0 +1,183 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::Debug;
+
+use cbor_enhanced::{from_bytes, to_vec, CborError, Deserialize, Serialize, Serializer};
+
+fn round_trip<T>(value: T, expected: &[u8])
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    let bytes = to_vec(&value);
+    assert_eq!(bytes, expected);
+    assert_eq!(from_bytes::<T>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_scalars() {
+    round_trip(7u8, b"\x07");
+    round_trip(true, b"\xf5");
+    round_trip('ü', b"\x62\xc3\xbc");
+    round_trip((), b"\xf6");
+    assert!(matches!(
+        from_bytes::<char>(b"\x62ab").unwrap_err().inner(),
+        CborError::ExpectChar(_)
+    ));
+}
+
+#[test]
+fn test_128_bit_integers() {
+    round_trip(u64::MAX as u128, b"\x1b\xff\xff\xff\xff\xff\xff\xff\xff");
+    round_trip(
+        u64::MAX as u128 + 1,
+        b"\xc2\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00",
+    );
+    round_trip(-1i128, b"\x20");
+    round_trip(
+        -(u64::MAX as i128) - 2,
+        b"\xc3\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00",
+    );
+    round_trip(u128::MAX, &[&[0xc2, 0x50][..], &[0xff; 16]].concat());
+    round_trip(i128::MIN, &[&[0xc3, 0x50, 0x7f][..], &[0xff; 15]].concat());
+
+    let too_large = [&[0xc2, 0x51, 0x01][..], &[0x00; 16]].concat();
+    assert!(matches!(
+        from_bytes::<u128>(&too_large).unwrap_err().inner(),
+        CborError::BignumOutOfRange("u128")
+    ));
+    assert!(matches!(
+        from_bytes::<i128>(&to_vec(&u128::MAX)).unwrap_err().inner(),
+        CborError::BignumOutOfRange("i128")
+    ));
+}
+
+#[test]
+fn test_byte_strings() {
+    round_trip(vec![1u8, 2], b"\x42\x01\x02");
+    round_trip([1u8, 2], b"\x42\x01\x02");
+    assert_eq!(from_bytes::<Vec<u8>>(b"\x82\x01\x02").unwrap(), vec![1, 2]);
+    assert_eq!(
+        from_bytes::<Vec<u8>>(b"\x5f\x41\x01\x41\x02\xff").unwrap(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn test_tuples_and_arrays() {
+    round_trip((1u16, "a".to_string(), false), b"\x83\x01\x61\x61\xf4");
+    round_trip([1u16, 2, 3], b"\x83\x01\x02\x03");
+    assert_eq!(
+        from_bytes::<(u16, bool)>(b"\x9f\x01\xf5\xff").unwrap(),
+        (1, true)
+    );
+    assert!(matches!(
+        from_bytes::<(u16, bool)>(b"\x83\x01\xf5\xf5")
+            .unwrap_err()
+            .inner(),
+        CborError::InvalidArrayLength { got: 3, .. }
+    ));
+    assert!(matches!(
+        from_bytes::<(u16, bool)>(b"\x9f\x01\xff")
+            .unwrap_err()
+            .inner(),
+        CborError::InvalidArrayLength { got: 1, .. }
+    ));
+    assert!(matches!(
+        from_bytes::<(u16, bool)>(b"\x9f\x01\xf5\xf5\xf5\xff")
+            .unwrap_err()
+            .inner(),
+        CborError::InvalidArrayLength { got: 4, .. }
+    ));
+    assert!(matches!(
+        from_bytes::<[u16; 3]>(b"\x82\x01\x02").unwrap_err().inner(),
+        CborError::InvalidArrayLength {
+            expected: [3],
+            got: 2
+        }
+    ));
+}
+
+#[test]
+fn test_collections() {
+    round_trip(VecDeque::from(vec![1u16, 2]), b"\x82\x01\x02");
+    round_trip(
+        vec![2u16, 1].into_iter().collect::<BTreeSet<_>>(),
+        b"\x82\x01\x02",
+    );
+    round_trip(vec![3u16].into_iter().collect::<HashSet<_>>(), b"\x81\x03");
+
+    let mut map = BTreeMap::new();
+    map.insert("b".to_string(), 2u16);
+    map.insert("a".to_string(), 1u16);
+    round_trip(map, b"\xa2\x61\x61\x01\x61\x62\x02");
+}
+
+#[test]
+fn test_wrappers() {
+    round_trip(Box::new(5u16), b"\x05");
+    let bytes = to_vec(&Cow::<'_, u16>::Owned(5));
+    assert_eq!(bytes, b"\x05");
+    assert_eq!(
+        from_bytes::<Cow<'_, u16>>(&bytes).unwrap(),
+        Cow::<u16>::Owned(5)
+    );
+    assert_eq!(to_vec(&Cow::Borrowed("a")), b"\x61\x61");
+    assert_eq!(to_vec(&Cow::Borrowed(&[1u8][..])), b"\x41\x01");
+}
+
+#[test]
+fn test_integer_narrowing() {
+    assert_eq!(from_bytes::<u8>(b"\x18\xff").unwrap(), 255);
+    assert!(matches!(
+        from_bytes::<u8>(b"\x19\x01\x2c").unwrap_err().inner(),
+        CborError::IntegerOutOfRange {
+            value: 300,
+            target: "u8"
+        }
+    ));
+    assert!(matches!(
+        from_bytes::<usize>(b"\x20").unwrap_err().inner(),
+        CborError::IntegerOutOfRange {
+            value: -1,
+            target: "usize"
+        }
+    ));
+    assert_eq!(from_bytes::<i8>(b"\x38\x7f").unwrap(), -128);
+    assert!(matches!(
+        from_bytes::<i8>(b"\x38\x80").unwrap_err().inner(),
+        CborError::IntegerOutOfRange {
+            value: -129,
+            target: "i8"
+        }
+    ));
+    assert!(matches!(
+        from_bytes::<i64>(&to_vec(&u64::MAX)).unwrap_err().inner(),
+        CborError::IntegerOutOfRange { target: "i64", .. }
+    ));
+}
+
+#[test]
+fn test_write_i64_beyond_64_bits() {
+    let mut serializer = Serializer::new();
+    serializer.write_i64(-(u64::MAX as i128) - 2);
+    assert_eq!(
+        serializer.get_bytes(),
+        b"\xc3\x49\x01\x00\x00\x00\x00\x00\x00\x00\x00"
+    );
+    assert_eq!(
+        from_bytes::<i128>(serializer.get_bytes()).unwrap(),
+        -(u64::MAX as i128) - 2
+    );
+}
+
+#[test]
+fn test_option() {
+    round_trip(Some(1u16), b"\x01");
+    round_trip(None::<u16>, b"\xf6");
+    round_trip(vec![None, Some(1u16)], b"\x82\xf6\x01");
+    assert_eq!(from_bytes::<Option<u16>>(b"\xf7").unwrap(), None);
+    assert!(matches!(
+        from_bytes::<Option<u16>>(b"\x61\x61").unwrap_err().inner(),
+        CborError::ExpectUnsigned(_)
+    ));
+}
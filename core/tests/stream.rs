@@ -0,0 +1,76 @@
+use std::io::{Cursor, Read};
+
+use cbor_enhanced::{CborError, Serializer, StreamDeserializer, Value};
+
+struct OneByteReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Read for OneByteReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.data.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.data[0];
+        self.data = &self.data[1..];
+        Ok(1)
+    }
+}
+
+fn three_items() -> Vec<u8> {
+    let mut serializer = Serializer::new();
+    serializer.write_u64(1000);
+    serializer.write_text("hello world");
+    serializer.write_array_def(2);
+    serializer.write_bytes(b"\x01\x02\x03");
+    serializer.write_f64(1.5);
+    serializer.get_bytes().to_vec()
+}
+
+#[test]
+fn test_stream_items() {
+    let bytes = three_items();
+    let mut stream = StreamDeserializer::new(Cursor::new(bytes));
+    assert_eq!(stream.next_item().unwrap().unwrap(), b"\x19\x03\xe8");
+    assert_eq!(
+        stream.next_deserialize::<&str>().unwrap().unwrap(),
+        "hello world"
+    );
+    match stream.next_value().unwrap().unwrap() {
+        Value::Array(vec) => assert_eq!(vec.len(), 2),
+        _ => panic!("expected array"),
+    }
+    assert!(stream.next_item().unwrap().is_none());
+}
+
+#[test]
+fn test_stream_partial_reads() {
+    let bytes = three_items();
+    let stream = StreamDeserializer::new(OneByteReader { data: &bytes }).with_chunk_size(1);
+    let items: Vec<Vec<u8>> = stream.map(|item| item.unwrap()).collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items.concat(), bytes);
+}
+
+#[test]
+fn test_stream_indefinite_items() {
+    let bytes = b"\x9f\x01\x7f\x61\x61\x61\x62\xff\xff\x02";
+    let mut stream = StreamDeserializer::new(OneByteReader { data: bytes });
+    assert_eq!(
+        stream.next_item().unwrap().unwrap(),
+        b"\x9f\x01\x7f\x61\x61\x61\x62\xff\xff"
+    );
+    assert_eq!(stream.next_deserialize::<u64>().unwrap().unwrap(), 2);
+    assert!(stream.next_item().unwrap().is_none());
+}
+
+#[test]
+fn test_stream_truncated() {
+    let bytes = b"\x01\x44\x01\x02";
+    let mut stream = StreamDeserializer::new(Cursor::new(bytes));
+    assert_eq!(stream.next_deserialize::<u64>().unwrap().unwrap(), 1);
+    match stream.next_item() {
+        Err(CborError::Incomplete(_)) => {}
+        other => panic!("expected incomplete but got {:?}", other),
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use cbor_enhanced::{CborError, Context, Deserialize, Deserializer, ReducedSpecial, Value};
+
+#[test]
+fn test_reserved_and_indefinite_heads() {
+    let deserializer = Deserializer::new();
+    for bytes in &[b"\x1c", b"\x3d", b"\x5e", b"\xfc"] {
+        match deserializer
+            .take_value(*bytes)
+            .map_err(|e| e.inner().clone())
+        {
+            Err(CborError::ReservedAdditionalInfo(_)) => {}
+            other => panic!("expected reserved error but got {:?}", other),
+        }
+    }
+    for bytes in &[b"\x1f", b"\x3f", b"\xdf"] {
+        match deserializer
+            .take_value(*bytes)
+            .map_err(|e| e.inner().clone())
+        {
+            Err(CborError::InvalidIndefiniteLength(_)) => {}
+            other => panic!("expected indefinite error but got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_non_shortest_heads() {
+    let lenient = Deserializer::new();
+    let strict = Deserializer::strict();
+    for bytes in &[
+        b"\x18\x17".as_ref(),
+        b"\x19\x00\xff",
+        b"\x3a\x00\x00\xff\xff",
+        b"\x1b\x00\x00\x00\x00\xff\xff\xff\xff",
+        b"\x78\x01\x61",
+        b"\x98\x00",
+        b"\xd8\x01\x00",
+    ] {
+        assert!(lenient.take_value(bytes).is_ok());
+        match strict.take_value(bytes).map_err(|e| e.inner().clone()) {
+            Err(CborError::NotShortestForm(_)) => {}
+            other => panic!("expected shortest form error but got {:?}", other),
+        }
+    }
+    assert!(strict.take_value(b"\x18\x18").is_ok());
+    assert!(strict
+        .take_value(b"\x1b\x00\x00\x00\x01\x00\x00\x00\x00")
+        .is_ok());
+}
+
+#[test]
+fn test_unexpected_break() {
+    let lenient = Deserializer::new();
+    let (value, _) = lenient.take_value(b"\xff").unwrap();
+    assert_eq!(value, Value::Special(ReducedSpecial::Break));
+    match Deserializer::strict()
+        .take_value(b"\xff")
+        .map_err(|e| e.inner().clone())
+    {
+        Err(CborError::UnexpectedBreak) => {}
+        other => panic!("expected unexpected break but got {:?}", other),
+    }
+    assert!(Deserializer::strict()
+        .take_value(b"\x9f\x01\x02\xff")
+        .is_ok());
+}
+
+#[test]
+fn test_duplicate_map_keys() {
+    let bytes = b"\xa2\x61\x61\x01\x61\x61\x02";
+    assert!(Deserializer::new().take_value(bytes).is_ok());
+    match Deserializer::strict()
+        .take_value(bytes)
+        .map_err(|e| e.inner().clone())
+    {
+        Err(CborError::DuplicateMapKey(key)) => assert_eq!(key, b"\x61\x61"),
+        other => panic!("expected duplicate key but got {:?}", other),
+    }
+
+    let mut deserializer = Deserializer::new();
+    let (map, _) =
+        HashMap::<&str, u64>::deserialize(&mut deserializer, bytes, &Context::new()).unwrap();
+    assert_eq!(map["a"], 2);
+    deserializer.set_strict(true);
+    let result = HashMap::<&str, u64>::deserialize(&mut deserializer, bytes, &Context::new());
+    assert!(matches!(result, Err(CborError::DuplicateMapKey(_))));
+}
+
+#[test]
+fn test_tag_content() {
+    let strict = Deserializer::strict();
+    assert!(strict.take_value(b"\xc1\x1a\x51\x4b\x67\xb0").is_ok());
+    assert!(strict.take_value(b"\xc2\x41\x01").is_ok());
+    for bytes in &[
+        b"\xc0\x01".as_ref(),
+        b"\xc1\x61\x61",
+        b"\xc2\x01",
+        b"\xc4\x01",
+    ] {
+        assert!(Deserializer::new().take_value(bytes).is_ok());
+        match strict.take_value(bytes).map_err(|e| e.inner().clone()) {
+            Err(CborError::InvalidTagContent(_, _)) => {}
+            other => panic!("expected invalid tag content but got {:?}", other),
+        }
+    }
+}
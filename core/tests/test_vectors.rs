@@ -1,6 +1,6 @@
 extern crate cbor_enhanced;
 
-use cbor_enhanced::{Deserializer, ReducedSpecial, Serializer, Value};
+use cbor_enhanced::{CborError, Deserializer, ReducedSpecial, Serializer, Value};
 use chrono::DateTime;
 use float_cmp::approx_eq;
 use half::f16;
@@ -216,6 +216,38 @@ fn test_special() {
     });
 }
 
+#[test]
+fn test_simple() {
+    [
+        (b"\xf0".as_ref(), 16u8),
+        (b"\xe0", 0),
+        (b"\xf8\x20", 32),
+        (b"\xf8\xff", 255),
+    ]
+    .iter()
+    .for_each(|(bytes, expected)| {
+        let deserializer = Deserializer::new();
+        let mut serializer = Serializer::new();
+        let (value, _) = deserializer.take_value(bytes).unwrap();
+        assert_eq!(value, Value::Simple(*expected));
+        assert_eq!(deserializer.take_simple(bytes, false).unwrap().0, *expected);
+        serializer.write_value(&value);
+        assert_eq!(serializer.as_ref(), *bytes);
+    });
+    let deserializer = Deserializer::new();
+    assert!(deserializer.take_value(b"\xf8\x18").is_err());
+    assert!(deserializer.take_value(b"\xf8\x1f").is_err());
+    assert!(deserializer.take_simple(b"\xf6", false).is_err());
+
+    let mut serializer = Serializer::new();
+    serializer.write_simple(24);
+    assert!(matches!(
+        serializer.error(),
+        Some(CborError::InvalidSimpleValue(24))
+    ));
+    assert!(serializer.as_ref().is_empty());
+}
+
 #[test]
 fn test_timestamp_string() {
     let bytes =
@@ -565,12 +597,120 @@ fn test_maps() {
 }
 
 #[test]
-fn test_fail_on_infinite() {
+fn test_infinite_strings() {
     let deserializer = Deserializer::new();
+    test_byte(
+        b"\x5f\x42\x01\x02\x43\x03\x04\x05\xff",
+        &[1u8, 2, 3, 4, 5],
+        true,
+    );
+    let (value, _) = deserializer
+        .take_value(b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff")
+        .unwrap();
+    assert_text(&value, "streaming");
     assert!(deserializer
-        .take_value(b"\x5f\x42\x01\x02\x43\x03\x04\x05\xff")
+        .take_value(b"\x5f\x42\x01\x02\x61\x61\xff")
         .is_err());
     assert!(deserializer
-        .take_value(b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff")
+        .take_value(b"\x7f\x7f\x61\x61\xff\xff")
         .is_err());
+    assert!(deserializer
+        .take_text(
+            b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff",
+            true
+        )
+        .is_err());
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_diagnostic_notation() {
+    let vectors: serde_json::Value =
+        serde_json::from_str(include_str!("test_vectors.json")).unwrap();
+    let mut checked = 0;
+    for vector in vectors.as_array().unwrap() {
+        let diagnostic = match vector["diagnostic"].as_str() {
+            Some(diagnostic) => diagnostic,
+            None => continue,
+        };
+        let hex = vector["hex"].as_str().unwrap();
+        // two byte simple values below 32 are not well-formed since RFC 8949
+        if hex == "f818" {
+            continue;
+        }
+        let bytes = from_hex(hex);
+        let deserializer = Deserializer::new();
+        let (rendered, remaining) = deserializer.take_diagnostic(&bytes).unwrap();
+        assert_eq!(without_float_indicators(&rendered), diagnostic, "{}", hex);
+        assert!(remaining.is_empty());
+        let (value, _) = deserializer.take_value(&bytes).unwrap();
+        if !diagnostic.contains('_') {
+            assert_eq!(value.to_string(), diagnostic, "{}", hex);
+        }
+        let parsed = Value::from_diagnostic(diagnostic).unwrap();
+        assert_eq!(parsed.to_string(), value.to_string(), "{}", hex);
+        checked += 1;
+    }
+    assert_eq!(checked, 22);
+}
+
+/// The vectors of RFC 8949 appendix A omit the encoding indicators of floats.
+fn without_float_indicators(diagnostic: &str) -> String {
+    let mut out = String::new();
+    let mut chars = diagnostic.chars().peekable();
+    while let Some(c) = chars.next() {
+        let after_number = out.ends_with(|p: char| p.is_ascii_alphanumeric());
+        if c == '_' && after_number && matches!(chars.peek(), Some('1'..='3')) {
+            chars.next();
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[test]
+fn test_diagnostic_float_indicators() {
+    let deserializer = Deserializer::new();
+    for (bytes, expected) in [
+        (&b"\xf9\x3c\x00"[..], "1.0_1"),
+        (b"\xfa\x47\xc3\x50\x00", "100000.0_2"),
+        (b"\xfb\x3f\xf1\x99\x99\x99\x99\x99\x9a", "1.1_3"),
+        (b"\xf9\x7c\x00", "Infinity_1"),
+        (b"\x82\xfa\x7f\xc0\x00\x00\xf9\x80\x00", "[NaN_2, -0.0_1]"),
+    ]
+    .iter()
+    {
+        let (rendered, _) = deserializer.take_diagnostic(bytes).unwrap();
+        assert_eq!(&rendered, expected);
+        let parsed = Value::from_diagnostic(&rendered).unwrap();
+        let (value, _) = deserializer.take_value(bytes).unwrap();
+        assert_eq!(parsed.to_string(), value.to_string());
+    }
+}
+
+#[test]
+fn test_diagnostic_indefinite() {
+    let deserializer = Deserializer::new();
+    let (rendered, _) = deserializer
+        .take_diagnostic(b"\xbf\x61\x61\x01\x61\x62\x9f\x02\x03\xff\xff")
+        .unwrap();
+    assert_eq!(rendered, "{_ \"a\": 1, \"b\": [_ 2, 3]}");
+    let (rendered, _) = deserializer
+        .take_diagnostic(b"\x7f\x65\x73\x74\x72\x65\x61\x64\x6d\x69\x6e\x67\xff")
+        .unwrap();
+    assert_eq!(rendered, "(_ \"strea\", \"ming\")");
+    let (value, _) = deserializer
+        .take_value(b"\x83\xfb\x7e\x37\xe4\x3c\x88\x00\x75\x9c\xf9\x00\x01\x62\x22\x5c")
+        .unwrap();
+    assert_eq!(
+        value.to_string(),
+        "[1.0e+300, 5.960464477539063e-8, \"\\\"\\\\\"]"
+    );
 }
@@ -0,0 +1,75 @@
+use cbor_enhanced::{cbor, Deserializer, IanaTag, ReducedSpecial, Serializer, Value};
+
+#[test]
+fn test_accessors() {
+    let bytes = b"\xa3\x61\x61\x82\x01\x20\x02\x43\x01\x02\x03\x61\x74\xc1\x1a\x51\x4b\x67\xb0";
+    let (value, _) = Deserializer::new().take_value(bytes).unwrap();
+    assert_eq!(value["a"][0].as_u64(), Some(1));
+    assert_eq!(value["a"][1].as_i128(), Some(-1));
+    assert_eq!(value["a"].as_array().map(|a| a.len()), Some(2));
+    assert_eq!(
+        value.get_integer(2).and_then(Value::as_bytes),
+        Some(b"\x01\x02\x03".as_ref())
+    );
+    assert_eq!(
+        value["t"].as_tag(),
+        Some((IanaTag::EpochBasedTime, &Value::U64(1363896240)))
+    );
+    assert_eq!(value.get_key(&Value::from("a")), Some(&value["a"]));
+    assert!(value.get_text("missing").is_none());
+    assert!(value.get(0).is_none());
+    assert!(value["a"][0].as_text().is_none());
+    assert_eq!(value.as_map().map(|m| m.len()), Some(3));
+}
+
+#[test]
+fn test_cbor_macro() {
+    let name = String::from("cbor");
+    let value = cbor!({
+        "name" => name,
+        1 => [1, -2, 1.5, null, [true, undefined]],
+        "time" => tag(1, 1363896240),
+        "empty" => {},
+        b"\x01\x02".as_ref() => Some(3u8),
+    });
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (Value::from("name"), Value::from("cbor")),
+            (
+                Value::U64(1),
+                Value::Array(vec![
+                    Value::U64(1),
+                    Value::I128(-2),
+                    Value::F64(1.5),
+                    Value::Special(ReducedSpecial::Null),
+                    Value::Array(vec![
+                        Value::Bool(true),
+                        Value::Special(ReducedSpecial::Undefined)
+                    ]),
+                ])
+            ),
+            (
+                Value::from("time"),
+                Value::Tag(IanaTag::EpochBasedTime, Box::new(Value::U64(1363896240)))
+            ),
+            (Value::from("empty"), Value::Map(vec![])),
+            (Value::from(b"\x01\x02".as_ref()), Value::U64(3)),
+        ])
+    );
+    assert_eq!(cbor!([]), Value::Array(vec![]));
+    assert_eq!(cbor!(['c', 42u128]), cbor!(["c", 42]));
+    assert_eq!(
+        Value::from(u64::MAX as u128 + 1),
+        Value::Tag(
+            IanaTag::PositiveBigNum,
+            Box::new(Value::from(
+                b"\x01\x00\x00\x00\x00\x00\x00\x00\x00".as_ref()
+            ))
+        )
+    );
+
+    let mut serializer = Serializer::new();
+    serializer.write_value(&cbor!([1, {"a" => -1}]));
+    assert_eq!(serializer.get_bytes(), b"\x82\x01\xa1\x61\x61\x20");
+}
@@ -33,7 +33,7 @@ pub(crate) fn generate_deserialize(input: &syn::DeriveInput) -> TokenStream {
         .map(|f| {
             let ty = &f.ty;
             let default = match &f.default {
-                FieldDefault::Option => quote!(None),
+                FieldDefault::Option => quote!(Some(None)),
                 FieldDefault::Default => {
                     let ty = to_non_generic_type(ty);
                     quote!(Some(#ty::default()))
@@ -74,15 +74,25 @@ pub(crate) fn generate_deserialize(input: &syn::DeriveInput) -> TokenStream {
             }
         };
         let ty = if ty_string == "Vec<u8>" {
-            quote! {let (val, rem) = deserializer.take_bytes(data, false).map(|(d,rem)|(Vec::from(d),rem))?;}
+            quote! {let (val, rem) = deserializer.take_bytes_cow(data, false).map(|(d,rem)|(d.into_owned(),rem))?;}
         } else {
             ty
         };
 
         let ident = &f.render_name;
+        let field_name = match &f.identifier {
+            Either::A(ident) => ident.to_string(),
+            Either::B(index) => index.index.to_string(),
+        };
         quote! {
             #field_id => {
-                #ty
+                let result = (|| -> Result<_, cbor_enhanced::CborError> {
+                    #ty
+                    Ok((val, rem))
+                })();
+                let (val, rem) = deserializer.locate(result, data, || {
+                    cbor_enhanced::PathSegment::Field(#field_id, #field_name)
+                })?;
                 data = rem;
                 #ident = Some(val.into());
                 found_ids.push(#field_id);
@@ -161,26 +171,34 @@ pub(crate) fn generate_deserialize(input: &syn::DeriveInput) -> TokenStream {
     let q = quote! {
         impl#main_generics cbor_enhanced::Deserialize#trait_generics for #identifier #type_generic #where_clause  {
             fn deserialize(deserializer: &mut cbor_enhanced::Deserializer, data: &#lifetime [u8], context: &cbor_enhanced::Context) -> Result<(Self, &#lifetime [u8]), cbor_enhanced::CborError> {
-                #(#declarations)*
-
-                let mut found_ids: Vec<u64> = Vec::new();
-                let (map_def, data) = deserializer.take_map_def(data, true)?;
-                let map_length = map_def.unwrap_or(0);
-                let mut data = data;
-                for i in 0..map_length {
-                    let (key, rem) = deserializer.take_unsigned(data, true)?;
-                    data = rem;
-                    match key {
-                        #(#collect_fields)*
-
-                        o => {
-                            found_ids.push(o);
+                deserializer.enter_nested(data)?;
+                let result = (|| -> Result<(Self, &#lifetime [u8]), cbor_enhanced::CborError> {
+                    #(#declarations)*
+
+                    let mut found_ids: Vec<u64> = Vec::new();
+                    let (map_def, data) = deserializer.take_map_def(data, true)?;
+                    let map_length = map_def.unwrap_or(0);
+                    deserializer.check_collection_length(map_length)?;
+                    let mut data = data;
+                    for i in 0..map_length {
+                        let (key, rem) = deserializer.take_unsigned(data, true)?;
+                        deserializer.check_duplicate_id(&found_ids, key, &data[..data.len() - rem.len()])?;
+                        data = rem;
+                        match key {
+                            #(#collect_fields)*
+
+                            o => {
+                                data = deserializer.skip_value(data)?;
+                                found_ids.push(o);
+                            }
                         }
                     }
-                }
 
-                #instantiation
-                Ok((retval, data))
+                    #instantiation
+                    Ok((retval, data))
+                })();
+                deserializer.leave_nested();
+                result
             }
         }
     };
@@ -30,7 +30,7 @@ impl<A: Clone, B: Clone> Either<A, B> {
     }
 }
 
-#[proc_macro_derive(cbor_protocol, attributes(reserved, default, id))]
+#[proc_macro_derive(cbor_protocol, attributes(reserved, default, id, omit_none))]
 pub fn derive_protocol(item: TokenStream) -> TokenStream {
     let parsed: syn::DeriveInput = syn::parse(item).unwrap();
 
@@ -8,21 +8,12 @@ pub(crate) fn generate_serialize(input: &syn::DeriveInput) -> TokenStream {
 
     let identifier = &input.ident;
     let generics = &input.generics;
-    let length = get_field_amount(&input.data);
     let serialized_fields = get_serialized_fields(&input.data, &input.ident, &mut checker);
 
-    let length_token = if let Some(length) = length {
-        quote! { serializer.write_map_def(#length);}
-    } else {
-        TokenStream::new()
-    };
-
     let (impl_generic, type_generic, where_clause) = generics.split_for_impl();
     quote! {
         impl#impl_generic cbor_enhanced::Serialize for #identifier#type_generic #where_clause {
             fn serialize(&self, serializer: &mut cbor_enhanced::Serializer, context: &cbor_enhanced::Context) {
-                #length_token
-
                 #(#serialized_fields)*
             }
         }
@@ -36,8 +27,13 @@ fn get_serialized_fields(
 ) -> Vec<TokenStream> {
     match data {
         Data::Struct(my_struct) => {
-            let fields = &my_struct.fields;
-            serialize_fields(fields, false, id_checker)
+            let fields = serialize_fields(&my_struct.fields, false, id_checker);
+            let map_def = write_map_def(&fields);
+            let field_token_stream = fields.into_iter().map(|f| f.tokens);
+            vec![quote! {
+                #map_def
+                #(#field_token_stream)*
+            }]
         }
         Data::Enum(my_enum) => {
             let variants: Vec<_> = my_enum
@@ -69,9 +65,13 @@ fn get_serialized_fields(
                 .collect();
             let variants: Vec<_> = variants
                 .iter()
-                .map(|(literal, identifier, field_token_stream, field_names)| {
-                    let len = field_token_stream.len().max(1);
-                    let map_def = quote! {serializer.write_map_def(#len);};
+                .map(|(literal, identifier, fields, field_names)| {
+                    let map_def = if fields.is_empty() {
+                        quote! {serializer.write_map_def(1);}
+                    } else {
+                        write_map_def(fields)
+                    };
+                    let field_token_stream: Vec<_> = fields.iter().map(|f| &f.tokens).collect();
                     let token = if let Some(id_literal) = literal {
                         id_checker.check_add_id_literal(&id_literal);
                         quote! {
@@ -129,7 +129,27 @@ fn get_serialized_fields(
                 }
             }]
         }
-        Data::Union(_my_union) => Vec::new(),
+        Data::Union(my_union) => {
+            let length = my_union.fields.named.len();
+            vec![quote! {serializer.write_map_def(#length);}]
+        }
+    }
+}
+
+struct SerializedField {
+    tokens: TokenStream,
+    /// Condition under which the field is left out of the map.
+    omit: Option<TokenStream>,
+}
+
+/// Writes the map head, fields which may be omitted are only counted at runtime.
+fn write_map_def(fields: &[SerializedField]) -> TokenStream {
+    let length = fields.len();
+    let omitted: Vec<_> = fields.iter().filter_map(|f| f.omit.as_ref()).collect();
+    if omitted.is_empty() {
+        quote! {serializer.write_map_def(#length);}
+    } else {
+        quote! {serializer.write_map_def(#length #(- (#omitted) as usize)*);}
     }
 }
 
@@ -165,8 +185,8 @@ fn serialize_fields(
     fields: &Fields,
     is_enum: bool,
     id_checker: &mut IdChecker,
-) -> Vec<TokenStream> {
-    fields
+) -> Vec<SerializedField> {
+    let mut fields = fields
         .iter()
         .enumerate()
         .map(|(field_id, f)| {
@@ -174,6 +194,7 @@ fn serialize_fields(
             let default_attribute = default_attribute
                 .filter(|a| !a.tokens.is_empty())
                 .map(|a| syn::parse2::<Group>(a.tokens.clone()).unwrap());
+            let omit_none = f.attrs.iter().any(|a| a.path.is_ident("omit_none"));
 
             let found = f.attrs.iter().find(|a| a.path.is_ident("id"));
             if let Some(attribute) = found {
@@ -189,7 +210,7 @@ fn serialize_fields(
                 } else {
                     Either::B(index)
                 };
-                (id, either, default_attribute)
+                (id, either, default_attribute, omit_none)
             } else {
                 f.span()
                     .unwrap()
@@ -198,7 +219,16 @@ fn serialize_fields(
                 unreachable!()
             }
         })
-        .map(|(id_literal, identifier, default_attribute)| {
+        .collect::<Vec<_>>();
+    // written in id order so the map keys are sorted like deterministic encoding requires
+    fields.sort_by_key(|(id_literal, ..)| {
+        syn::parse2::<syn::LitInt>(quote! {#id_literal})
+            .and_then(|parsed| parsed.base10_parse::<u64>())
+            .ok()
+    });
+    fields
+        .into_iter()
+        .map(|(id_literal, identifier, default_attribute, omit_none)| {
             let parsed: syn::LitInt = syn::parse(quote! {#id_literal}.into()).unwrap();
             let id = parsed
                 .base10_parse::<usize>()
@@ -225,46 +255,36 @@ fn serialize_fields(
                     }
                 }
             };
-            if let Some(default_attribute) = default_attribute {
-                let tokens = &default_attribute.stream();
-                if is_enum {
-                    quote! {
-                        if #identifier != #tokens {
-                            serializer.write_u64(#id_literal);
-                            #identifier.serialize(serializer, context);
-                        }
-                    }
-                } else {
-                    quote! {
-                        if self.#identifier != #tokens {
-                            serializer.write_u64(#id_literal);
-                            self.#identifier.serialize(serializer, context);
-                        }
-                    }
-                }
-            } else if is_enum {
-                quote! {
-                    serializer.write_u64(#id_literal);
-                    #identifier.serialize(serializer, context);
-                }
+            let field = if is_enum {
+                quote!(#identifier)
             } else {
-                quote! {
-                    serializer.write_u64(#id_literal);
-                    self.#identifier.serialize(serializer, context);
-                }
-            }
+                quote!(self.#identifier)
+            };
+            let omit = if omit_none {
+                Some(quote! {#field.is_none()})
+            } else {
+                default_attribute.map(|default_attribute| {
+                    let tokens = &default_attribute.stream();
+                    quote! {#field == #tokens}
+                })
+            };
+            let write = quote! {
+                serializer.write_u64(#id_literal);
+                #field.serialize(serializer, context);
+            };
+            let tokens = match &omit {
+                Some(omit) => quote! {
+                    if !(#omit) {
+                        #write
+                    }
+                },
+                None => write,
+            };
+            SerializedField { tokens, omit }
         })
         .collect()
 }
 
-fn get_field_amount(data: &Data) -> Option<usize> {
-    match data {
-        Data::Struct(my_struct) => Some(my_struct.fields.len()),
-        Data::Enum(_) => None,
-        Data::Union(my_union) => Some(my_union.fields.named.len()),
-    }
-}
-
 fn get_reserved_ids(attrs: &[Attribute]) -> Vec<usize> {
     let found = attrs.iter().find(|a| a.path.is_ident("reserved")).map(|a| {
         let group: Group = syn::parse2(a.tokens.clone()).unwrap();